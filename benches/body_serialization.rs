@@ -0,0 +1,71 @@
+//! Cost of building the `sendBundle` request body for a bundle of five
+//! near-maximum-size transactions. Run with `cargo bench --bench
+//! body_serialization`.
+//!
+//! `previous path` is the encoding before request bodies were serialized
+//! once: the body `Value` encoded for the wire and pretty-printed again for
+//! the trace log.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use jito_sdk_rust::bundle::BundleBuilder;
+use serde_json::json;
+use solana_hash::Hash;
+use solana_instruction::Instruction;
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+
+const ITERATIONS: u32 = 20_000;
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    for _ in 0..ITERATIONS / 10 {
+        f();
+    }
+    let started = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let per_iter: Duration = started.elapsed() / ITERATIONS;
+    println!("{:<40} {:>10.2?}/iter", name, per_iter);
+}
+
+fn main() {
+    let payer = Keypair::new();
+    // 1,000 bytes of instruction data keeps each transaction just under the
+    // 1,232-byte packet limit.
+    let groups = (0..5u8)
+        .map(|i| {
+            vec![Instruction::new_with_bytes(
+                Pubkey::new_unique(),
+                &[i; 1000],
+                vec![],
+            )]
+        })
+        .collect();
+    let bundle =
+        BundleBuilder::from_instruction_groups(groups, &payer.pubkey(), &[&payer], Hash::default())
+            .expect("bundle builds");
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "sendBundle",
+        "params": bundle.build().expect("bundle encodes"),
+    });
+    println!(
+        "body size: {} bytes",
+        bundle.body_bytes().expect("bundle encodes").len()
+    );
+
+    bench("body_bytes (encode transactions + body)", || {
+        black_box(bundle.body_bytes().unwrap());
+    });
+    bench("single encode of the body Value", || {
+        black_box(serde_json::to_vec(black_box(&body)).unwrap());
+    });
+    bench("previous path (encode + pretty-print)", || {
+        black_box(serde_json::to_vec(black_box(&body)).unwrap());
+        black_box(serde_json::to_string_pretty(black_box(&body)).unwrap());
+    });
+}
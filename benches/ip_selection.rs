@@ -0,0 +1,78 @@
+//! Cost of picking a client with `IpSelectAlgorithm::Random` for pools of
+//! 2, 10 and 100 IPs. Run with `cargo bench --bench ip_selection`.
+//!
+//! `allocating` is the selection used before, which collected the
+//! candidate indices into a `Vec` on every call; `get_client` is the current
+//! one, scanning pools of up to 8 IPs and rerolling in larger ones.
+
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use jito_sdk_rust::http_client::{HttpClient, IpSelectAlgorithm};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use reqwest::Client;
+
+const ITERATIONS: u32 = 1_000_000;
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    for _ in 0..ITERATIONS / 10 {
+        f();
+    }
+    let started = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let per_iter: Duration = started.elapsed() / ITERATIONS;
+    println!("{:<28} {:>10.2?}/iter", name, per_iter);
+}
+
+// The selection `get_client` used before, kept here for comparison.
+struct AllocatingPool {
+    clients: Vec<Client>,
+    healthy: Vec<AtomicBool>,
+    last: Mutex<Option<usize>>,
+}
+
+impl AllocatingPool {
+    fn get_client(&self) -> Client {
+        let len = self.clients.len();
+        let any_healthy = self.healthy.iter().any(|h| h.load(Ordering::Relaxed));
+        let is_usable = |i: usize| !any_healthy || self.healthy[i].load(Ordering::Relaxed);
+
+        let mut last = self.last.lock().unwrap();
+        let candidates: Vec<usize> = (0..len)
+            .filter(|&i| Some(i) != *last && is_usable(i))
+            .collect();
+        let selected = if candidates.is_empty() {
+            (*last)
+                .filter(|&i| is_usable(i))
+                .unwrap_or_else(|| rand::thread_rng().gen_range(0..len))
+        } else {
+            *candidates.choose(&mut rand::thread_rng()).unwrap()
+        };
+        *last = Some(selected);
+        self.clients[selected].clone()
+    }
+}
+
+fn main() {
+    for len in [2, 10, 100] {
+        let clients = vec![Client::new(); len];
+        let allocating = AllocatingPool {
+            clients: clients.clone(),
+            healthy: (0..len).map(|_| AtomicBool::new(true)).collect(),
+            last: Mutex::new(None),
+        };
+        let pool = HttpClient::from_clients(clients, IpSelectAlgorithm::Random);
+
+        bench(&format!("{:>3} IPs, allocating", len), || {
+            black_box(allocating.get_client());
+        });
+        bench(&format!("{:>3} IPs, get_client", len), || {
+            black_box(pool.get_client());
+        });
+    }
+}
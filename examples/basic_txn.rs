@@ -5,7 +5,7 @@ use solana_client::rpc_client::RpcClient;
 use solana_pubkey::Pubkey;
 use solana_keypair::Keypair;
 use solana_signer::{Signer, EncodableKey};
-use solana_program::system_instruction;
+use solana_system_interface::instruction as system_instruction;
 use solana_transaction::Transaction;
 use solana_instruction::Instruction;
 
@@ -71,10 +71,6 @@ async fn main() -> Result<()> {
             .unwrap_or_else(|_| "11111111111111111111111111111112".to_string()) // System Program as default
     )?;
     
-    // Get Jito tip account
-    let random_tip_account = jito_sdk.get_random_tip_account().await?;
-    let jito_tip_account = Pubkey::from_str(&random_tip_account)?;
-
     // Define amounts to send (in lamports)
     let main_transfer_amount = 1_000; // 0.000001 SOL
     let jito_tip_amount = 3_000; // 0.000003 SOL
@@ -91,17 +87,16 @@ async fn main() -> Result<()> {
         vec![],
     );
 
-    // Create transfer instructions - system_instruction is in solana-program
+    // Create transfer instructions - system_instruction is in solana-system-interface
     let main_transfer_ix = system_instruction::transfer(
         &sender.pubkey(),
         &receiver,
         main_transfer_amount,
     );
-    let jito_tip_ix = system_instruction::transfer(
-        &sender.pubkey(),
-        &jito_tip_account,
-        jito_tip_amount,
-    );
+    // Tip a random Jito tip account
+    let jito_tip_ix = jito_sdk
+        .build_tip_instruction(&sender.pubkey(), jito_tip_amount)
+        .await?;
 
     // Create transaction with all instructions
     let mut transaction = Transaction::new_with_payer(
@@ -0,0 +1,656 @@
+use anyhow::{anyhow, Result};
+
+use crate::http_client::{
+    AuthKeys, ClientOptions, ConnectionLimiter, DualStackPreference, FailoverOrder, HttpClient,
+    IpSelectAlgorithm, RequestInterceptor, RequestTimeouts, RetryPolicy, DEFAULT_REQUEST_TIMEOUT,
+};
+use crate::labels::LabelIndex;
+use crate::queue::BundleQueue;
+use crate::region::Network;
+use crate::submissions::RecentSubmissions;
+use crate::tip::{DefaultTip, TipAccountStrategy, TipCheck};
+use crate::{parse_ips, JitoJsonRpcSDK};
+use reqwest::{Client, RequestBuilder, Url};
+use serde::Serialize;
+#[cfg(feature = "solana-rpc")]
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::time::Duration;
+
+/// A problem found by `JitoSdkBuilder::validate`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConfigError {
+    #[error("base URL is empty")]
+    EmptyBaseUrl,
+    #[error("base URL {0} is not a valid http(s) URL")]
+    InvalidBaseUrl(String),
+    #[error("base URL {0} ends with '/', endpoint paths would be doubled")]
+    TrailingSlash(String),
+    #[error("UUID {0} is not a valid UUID")]
+    MalformedUuid(String),
+    #[error("IP pool entry {0} is not a valid IP address")]
+    InvalidIp(String),
+    #[error("auth_keys was given an empty key list")]
+    EmptyAuthKeys,
+    #[error("tip_account_allowlist was given an empty account list")]
+    EmptyTipAccountAllowlist,
+    #[error("max_connections_per_region must be at least 1")]
+    ZeroConnectionLimit,
+    #[error("bundle_queue_concurrency must be at least 1")]
+    ZeroQueueConcurrency,
+    #[error("invalid timeout: {0}")]
+    InvalidTimeout(String),
+    #[error("certificate fingerprint {0} is not a hex SHA-256 digest")]
+    InvalidCertFingerprint(String),
+    #[error("certificate pinning needs https, but {0} is plain http")]
+    PinnedPlainHttp(String),
+    #[error("Solana RPC URL {0} is not a valid http(s) URL")]
+    InvalidSolanaRpcUrl(String),
+}
+
+/// Effective configuration of a `JitoJsonRpcSDK`, from `config_summary`,
+/// meant for logging at startup. Secrets are never included: the UUID and
+/// auth keys are only reported as configured or not, URLs lose any
+/// credentials and query string, and the Solana RPC URL, which often embeds
+/// an API key, is omitted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigSummary {
+    pub base_url: String,
+    pub fallback_urls: Vec<String>,
+    /// Host of the base URL, which identifies the block engine region.
+    pub region: Option<String>,
+    pub uuid_configured: bool,
+    pub auth_keys_configured: bool,
+    pub default_timeout_ms: Option<u64>,
+    pub method_timeouts_ms: BTreeMap<String, u64>,
+    pub connection_retries: u32,
+    /// Retries of rate limited (429) requests, see `RetryPolicy`.
+    pub rate_limit_retries: u32,
+    /// Number of IPs in the pool, or `None` without one.
+    pub ip_count: Option<usize>,
+    pub max_connections_per_region: Option<usize>,
+    pub connect_timeout_ms: Option<u64>,
+    /// Whether connections race IPv4 and IPv6, see `happy_eyeballs_enabled`.
+    pub happy_eyeballs: bool,
+    pub jsonrpc_version: String,
+    pub solana_rpc_configured: bool,
+    pub cert_pinned: bool,
+    pub max_tip_lamports: Option<u64>,
+}
+
+impl ConfigSummary {
+    // Drops credentials and query parameters, which may carry tokens.
+    pub(crate) fn redact_url(url: &str) -> String {
+        match Url::parse(url) {
+            Ok(mut url) => {
+                let _ = url.set_username("");
+                let _ = url.set_password(None);
+                url.set_query(None);
+                url.to_string().trim_end_matches('/').to_string()
+            }
+            Err(_) => "<unparseable>".to_string(),
+        }
+    }
+}
+
+/// Builder for `JitoJsonRpcSDK` when more than the base URL and UUID need to
+/// be configured.
+#[derive(Debug, Clone, Default)]
+pub struct JitoSdkBuilder {
+    base_url: String,
+    fallback_urls: Vec<String>,
+    failover_order: FailoverOrder,
+    uuid: Option<String>,
+    auth_keys: Option<Vec<String>>,
+    tip_account_allowlist: Option<Vec<Pubkey>>,
+    default_tip: Option<DefaultTip>,
+    tip_check: TipCheck,
+    max_tip_lamports: Option<u64>,
+    tip_account_strategy: TipAccountStrategy,
+    network: Network,
+    ip_pool: Option<(Vec<String>, IpSelectAlgorithm)>,
+    dual_stack_preference: DualStackPreference,
+    connect_timeout: Option<Duration>,
+    #[cfg(feature = "cert-pinning")]
+    cert_sha256: Vec<String>,
+    max_connections_per_region: Option<usize>,
+    bundle_queue_concurrency: Option<usize>,
+    request_interceptor: Option<RequestInterceptor>,
+    timeouts: RequestTimeouts,
+    connection_retries: u32,
+    retry_policy: RetryPolicy,
+    jsonrpc_version: Option<String>,
+    validate_on_build: bool,
+    label_index_capacity: Option<usize>,
+    recent_submissions_capacity: Option<usize>,
+    #[cfg(feature = "solana-rpc")]
+    solana_rpc_url: Option<String>,
+    #[cfg(feature = "solana-rpc")]
+    payer_balance_reserve: Option<u64>,
+}
+
+impl JitoSdkBuilder {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Primary endpoint followed by fallbacks, replacing the base URL given
+    /// to `new`. A request that fails to connect or gets a 5xx response is
+    /// retried against the next endpoint, as `failover_order` says; unlike a
+    /// broadcast, each request is served by exactly one endpoint. Requests
+    /// start at the endpoint that last served one successfully. An empty
+    /// list leaves the base URL unchanged.
+    pub fn endpoints(mut self, urls: Vec<String>) -> Self {
+        let mut urls = urls.into_iter();
+        if let Some(primary) = urls.next() {
+            self.base_url = primary;
+            self.fallback_urls = urls.collect();
+        }
+        self
+    }
+
+    /// Order in which fallback endpoints are tried. Defaults to
+    /// `FailoverOrder::Sequential`.
+    pub fn failover_order(mut self, order: FailoverOrder) -> Self {
+        self.failover_order = order;
+        self
+    }
+
+    pub fn uuid(mut self, uuid: impl Into<String>) -> Self {
+        self.uuid = Some(uuid.into());
+        self
+    }
+
+    /// API keys sent in the `x-jito-auth` header, rotated round-robin per
+    /// request so several approved keys share the load.
+    pub fn auth_keys(mut self, keys: Vec<String>) -> Self {
+        self.auth_keys = Some(keys);
+        self
+    }
+
+    /// Restricts tipping to these accounts. Helpers that pick a tip account,
+    /// including `get_random_tip_account`, only choose from fetched accounts
+    /// that are also on the list, and fail with
+    /// `JitoError::NoAllowedTipAccounts` when none are.
+    pub fn tip_account_allowlist(mut self, accounts: Vec<Pubkey>) -> Self {
+        self.tip_account_allowlist = Some(accounts);
+        self
+    }
+
+    /// Tip `send_transfers` pays when called without an explicit amount; an
+    /// explicit amount always wins. Helpers that take a required amount,
+    /// such as `build_tip_instruction`, `add_tip_to_transaction` and
+    /// `tip_instructions_split`, don't consult it. Pass them
+    /// `resolve_tip(None)` to use the default there too.
+    pub fn default_tip(mut self, tip: DefaultTip) -> Self {
+        self.default_tip = Some(tip);
+        self
+    }
+
+    /// Shorthand for `default_tip(DefaultTip::Lamports(lamports))`.
+    pub fn default_tip_lamports(self, lamports: u64) -> Self {
+        self.default_tip(DefaultTip::Lamports(lamports))
+    }
+
+    /// What `send_txn` does with a bundle-only transaction that doesn't tip
+    /// a tip account: nothing, log a warning (the default), or fail with
+    /// `JitoError::MissingTip` before sending. The check fetches the tip
+    /// accounts once if they haven't been fetched yet.
+    pub fn tip_check(mut self, check: TipCheck) -> Self {
+        self.tip_check = check;
+        self
+    }
+
+    /// Largest total tip a bundle may pay. Bundles fail with
+    /// `JitoError::TipTooHigh` before sending if their tip transfers add up
+    /// to more, whether built by helpers such as `submit_competitive`,
+    /// passed to `send_bundle` as encoded params or submitted with
+    /// `send_bundle_prepared`. Tips paid to an account
+    /// loaded from a lookup table aren't counted, and `send_txn` isn't
+    /// checked. No cap by default; automated senders should set one well
+    /// above their largest intended tip so a pricing bug can't overtip.
+    pub fn max_tip_lamports(mut self, cap: u64) -> Self {
+        self.max_tip_lamports = Some(cap);
+        self
+    }
+
+    /// How random tip account selection picks an account. Defaults to
+    /// `TipAccountStrategy::Random`.
+    pub fn tip_account_strategy(mut self, strategy: TipAccountStrategy) -> Self {
+        self.tip_account_strategy = strategy;
+        self
+    }
+
+    /// Network the block engine serves. Picks the static tip accounts
+    /// `get_random_tip_account_within` falls back to when `getTipAccounts`
+    /// fails. Defaults to `Network::Mainnet`.
+    pub fn network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
+    pub fn ip_pool(mut self, ips: Vec<String>, algorithm: IpSelectAlgorithm) -> Self {
+        self.ip_pool = Some((ips, algorithm));
+        self
+    }
+
+    /// Address family to connect over when the block engine host has both
+    /// A and AAAA records. Defaults to the system resolver's order.
+    pub fn dual_stack_preference(mut self, preference: DualStackPreference) -> Self {
+        self.dual_stack_preference = preference;
+        self
+    }
+
+    /// Limit for establishing a connection to the block engine. When the
+    /// host has several addresses of one family, each gets an equal share,
+    /// so a slow address is abandoned early instead of delaying the next.
+    /// No limit by default.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Pins the block engine's TLS certificate to a SHA-256 fingerprint of
+    /// its DER encoding, as hex with optional `:` separators. Obtain it with
+    ///
+    /// ```text
+    /// openssl s_client -connect mainnet.block-engine.jito.wtf:443 </dev/null \
+    ///     | openssl x509 -outform DER | sha256sum
+    /// ```
+    ///
+    /// The certificate is checked during the TLS handshake of every
+    /// connection the SDK opens, so on a mismatch nothing, not even the
+    /// auth header or UUID, is sent, and the call fails with
+    /// `JitoError::CertificatePinMismatch`. Call again to allow more
+    /// certificates: a backup during rotation, or that of
+    /// `bundles.jito.wtf`, which serves `get_tip_floor` and
+    /// `bundle_id_for_signature` and is pinned the same way. Pinned
+    /// connections use rustls instead of the platform TLS library, and
+    /// every endpoint must be https.
+    #[cfg(feature = "cert-pinning")]
+    pub fn pin_cert_sha256(mut self, fingerprint: impl Into<String>) -> Self {
+        self.cert_sha256.push(fingerprint.into());
+        self
+    }
+
+    /// Caps concurrent requests to each block engine region (base URL).
+    /// Requests beyond the cap wait for a slot instead of opening new
+    /// connections. Unlimited by default.
+    pub fn max_connections_per_region(mut self, max: usize) -> Self {
+        self.max_connections_per_region = Some(max);
+        self
+    }
+
+    /// Bundles `enqueue_bundle` submits at once; the rest wait, highest
+    /// priority first. Defaults to `max_connections_per_region` if set,
+    /// otherwise 1.
+    pub fn bundle_queue_concurrency(mut self, concurrency: usize) -> Self {
+        self.bundle_queue_concurrency = Some(concurrency);
+        self
+    }
+
+    /// Solana RPC node used by helpers that need chain state, such as
+    /// fetching a fresh blockhash.
+    #[cfg(feature = "solana-rpc")]
+    pub fn solana_rpc_url(mut self, url: &str) -> Self {
+        self.solana_rpc_url = Some(url.to_string());
+        self
+    }
+
+    /// Makes `send_transfers` check the payer's balance before submitting,
+    /// failing with `JitoError::InsufficientFunds` unless it covers the
+    /// transfers, the tip, the transaction fees and `reserve_lamports` left
+    /// over for rent. Costs extra RPC round trips, so it is off by default.
+    /// Only `send_transfers` is checked: it is the one helper that builds
+    /// every transfer in the bundle itself. Bundles signed by the caller can
+    /// move lamports in ways the SDK can't total up, so they are sent
+    /// unchecked.
+    #[cfg(feature = "solana-rpc")]
+    pub fn payer_balance_check(mut self, reserve_lamports: u64) -> Self {
+        self.payer_balance_reserve = Some(reserve_lamports);
+        self
+    }
+
+    /// Timeout applied to every request without a `method_timeout`
+    /// override, including those outside the JSON-RPC API such as the tip
+    /// floor. Defaults to `DEFAULT_REQUEST_TIMEOUT`. Block engine requests
+    /// that run out of time fail with `JitoError::Timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.set_default(timeout);
+        self
+    }
+
+    /// Timeout for requests to one JSON-RPC method, e.g.
+    /// `method_timeout("getInflightBundleStatuses", Duration::from_millis(500))`.
+    pub fn method_timeout(mut self, method: &str, timeout: Duration) -> Self {
+        self.timeouts.set_method(method, timeout);
+        self
+    }
+
+    /// Extra attempts for requests that fail to connect, which never reached
+    /// the engine and are retried even for submissions. Timeouts are only
+    /// retried for read-only methods. Off by default.
+    pub fn connection_retries(mut self, retries: u32) -> Self {
+        self.connection_retries = retries;
+        self
+    }
+
+    /// How responses of 429 Too Many Requests are retried. Defaults to three
+    /// retries starting at 100 ms; `RetryPolicy::none()` turns them off.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Overrides the `jsonrpc` field of block engine requests. Defaults to
+    /// `DEFAULT_JSONRPC_VERSION`.
+    pub fn jsonrpc_version(mut self, version: impl Into<String>) -> Self {
+        self.jsonrpc_version = Some(version.into());
+        self
+    }
+
+    /// Hook invoked on every request right before `.send()`. It runs after
+    /// the SDK has set its own headers, auth and JSON body, so anything it
+    /// adds or overrides is what goes on the wire.
+    pub fn request_interceptor(
+        mut self,
+        interceptor: impl Fn(RequestBuilder) -> RequestBuilder + Send + Sync + 'static,
+    ) -> Self {
+        self.request_interceptor = Some(RequestInterceptor::new(interceptor));
+        self
+    }
+
+    /// Number of labeled bundles `send_bundle_labeled` remembers before
+    /// evicting the oldest. Defaults to `DEFAULT_LABEL_INDEX_CAPACITY`.
+    pub fn label_index_capacity(mut self, capacity: usize) -> Self {
+        self.label_index_capacity = Some(capacity);
+        self
+    }
+
+    /// Keeps the last `capacity` submitted bundle IDs for
+    /// `recent_submissions`. Off by default.
+    pub fn recent_submissions_capacity(mut self, capacity: usize) -> Self {
+        self.recent_submissions_capacity = Some(capacity);
+        self
+    }
+
+    /// Runs `validate` in `build` and fails with every problem found.
+    /// Off by default, in which case only settings that can't work at all
+    /// are rejected.
+    pub fn validate_on_build(mut self, enabled: bool) -> Self {
+        self.validate_on_build = enabled;
+        self
+    }
+
+    /// Checks the assembled configuration and reports all problems at once,
+    /// so misconfigurations surface at startup instead of on the first
+    /// request.
+    pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::new();
+
+        errors.extend(
+            std::iter::once(&self.base_url)
+                .chain(&self.fallback_urls)
+                .filter_map(|url| base_url_error(url)),
+        );
+
+        if let Some(uuid) = &self.uuid {
+            if !is_uuid(uuid) {
+                errors.push(ConfigError::MalformedUuid(uuid.clone()));
+            }
+        }
+
+        if self.auth_keys.as_ref().is_some_and(Vec::is_empty) {
+            errors.push(ConfigError::EmptyAuthKeys);
+        }
+
+        if self
+            .tip_account_allowlist
+            .as_ref()
+            .is_some_and(Vec::is_empty)
+        {
+            errors.push(ConfigError::EmptyTipAccountAllowlist);
+        }
+
+        if let Some((ips, _)) = &self.ip_pool {
+            errors.extend(
+                ips.iter()
+                    .filter(|ip| ip.parse::<IpAddr>().is_err())
+                    .map(|ip| ConfigError::InvalidIp(ip.clone())),
+            );
+        }
+
+        if self.max_connections_per_region == Some(0) {
+            errors.push(ConfigError::ZeroConnectionLimit);
+        }
+        if self.bundle_queue_concurrency == Some(0) {
+            errors.push(ConfigError::ZeroQueueConcurrency);
+        }
+
+        let timeout = self.timeouts.default_timeout();
+        if timeout == Some(Duration::ZERO) {
+            errors.push(ConfigError::InvalidTimeout(
+                "timeout must be greater than zero".to_string(),
+            ));
+        }
+        let mut zero_methods: Vec<&String> = self
+            .timeouts
+            .per_method()
+            .iter()
+            .filter(|(_, timeout)| timeout.is_zero())
+            .map(|(method, _)| method)
+            .collect();
+        zero_methods.sort();
+        errors.extend(zero_methods.into_iter().map(|method| {
+            ConfigError::InvalidTimeout(format!(
+                "method_timeout for {} must be greater than zero",
+                method
+            ))
+        }));
+        if self.connect_timeout == Some(Duration::ZERO) {
+            errors.push(ConfigError::InvalidTimeout(
+                "connect_timeout must be greater than zero".to_string(),
+            ));
+        }
+        if let (Some(connect_timeout), Some(timeout)) = (self.connect_timeout, timeout) {
+            if connect_timeout > timeout {
+                errors.push(ConfigError::InvalidTimeout(format!(
+                    "connect_timeout {:?} exceeds timeout {:?}",
+                    connect_timeout, timeout
+                )));
+            }
+        }
+
+        #[cfg(feature = "cert-pinning")]
+        if !self.cert_sha256.is_empty() {
+            errors.extend(
+                self.cert_sha256
+                    .iter()
+                    .filter(|fingerprint| parse_fingerprint(fingerprint).is_none())
+                    .map(|fingerprint| ConfigError::InvalidCertFingerprint(fingerprint.clone())),
+            );
+            errors.extend(
+                self.plain_http_endpoints()
+                    .map(|url| ConfigError::PinnedPlainHttp(url.clone())),
+            );
+        }
+
+        #[cfg(feature = "solana-rpc")]
+        if let Some(url) = &self.solana_rpc_url {
+            if !is_http_url(url) {
+                errors.push(ConfigError::InvalidSolanaRpcUrl(url.clone()));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    // Endpoints a pinned certificate can't protect.
+    #[cfg(feature = "cert-pinning")]
+    fn plain_http_endpoints(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.base_url)
+            .chain(&self.fallback_urls)
+            .filter(|url| url.starts_with("http://"))
+    }
+
+    pub fn build(self) -> Result<JitoJsonRpcSDK> {
+        if self.validate_on_build {
+            self.validate().map_err(|errors| {
+                let problems: Vec<String> = errors.iter().map(ToString::to_string).collect();
+                anyhow!("Invalid SDK configuration: {}", problems.join("; "))
+            })?;
+        }
+
+        #[cfg(feature = "cert-pinning")]
+        if !self.cert_sha256.is_empty() {
+            if let Some(url) = self.plain_http_endpoints().next() {
+                return Err(anyhow!(
+                    "certificate pinning needs https, but {} is plain http",
+                    url
+                ));
+            }
+        }
+
+        #[cfg_attr(not(feature = "cert-pinning"), allow(unused_mut))]
+        let mut options = ClientOptions {
+            dual_stack_preference: self.dual_stack_preference,
+            connect_timeout: self.connect_timeout,
+            timeout: self.timeouts.default_timeout(),
+            #[cfg(feature = "cert-pinning")]
+            cert_pins: Vec::new(),
+        };
+        let mut sdk = JitoJsonRpcSDK::new(&self.base_url, self.uuid);
+        sdk.fallback_urls = self.fallback_urls;
+        sdk.failover_order = self.failover_order;
+
+        #[cfg(feature = "cert-pinning")]
+        if !self.cert_sha256.is_empty() {
+            options.cert_pins = self
+                .cert_sha256
+                .iter()
+                .map(|fingerprint| {
+                    parse_fingerprint(fingerprint).ok_or_else(|| {
+                        anyhow!(
+                            "certificate fingerprint {} is not a hex SHA-256 digest",
+                            fingerprint
+                        )
+                    })
+                })
+                .collect::<Result<_>>()?;
+            sdk.cert_pins = options.cert_pins.clone();
+        }
+        #[cfg(feature = "cert-pinning")]
+        let pinned = !options.cert_pins.is_empty();
+        #[cfg(not(feature = "cert-pinning"))]
+        let pinned = false;
+
+        if options.dual_stack_preference != DualStackPreference::System
+            || pinned
+            || options.connect_timeout.is_some()
+            || options.timeout != Some(DEFAULT_REQUEST_TIMEOUT)
+        {
+            sdk.client = options.apply(Client::builder()).build()?;
+        }
+        if let Some((ips, algorithm)) = self.ip_pool {
+            sdk.client_pool = Some(HttpClient::new_with_options(
+                parse_ips(ips)?,
+                algorithm,
+                options,
+            )?);
+        }
+
+        if let Some(max) = self.max_connections_per_region {
+            if max == 0 {
+                return Err(anyhow!("max_connections_per_region must be at least 1"));
+            }
+            sdk.connection_limiter = Some(ConnectionLimiter::new(max));
+        }
+
+        let queue_concurrency = self
+            .bundle_queue_concurrency
+            .or(self.max_connections_per_region)
+            .unwrap_or(1);
+        if queue_concurrency == 0 {
+            return Err(anyhow!("bundle_queue_concurrency must be at least 1"));
+        }
+        sdk.bundle_queue = BundleQueue::new(queue_concurrency);
+
+        sdk.request_interceptor = self.request_interceptor;
+        sdk.timeouts = self.timeouts;
+        sdk.auth_keys = self.auth_keys.and_then(AuthKeys::new);
+        sdk.tip_account_allowlist = self.tip_account_allowlist;
+        sdk.default_tip = self.default_tip;
+        sdk.tip_check = self.tip_check;
+        sdk.max_tip_lamports = self.max_tip_lamports;
+        sdk.tip_account_strategy = self.tip_account_strategy;
+        sdk.network = self.network;
+        sdk.connection_retries = self.connection_retries;
+        sdk.retry_policy = self.retry_policy;
+        sdk.connect_timeout = self.connect_timeout;
+        if let Some(version) = self.jsonrpc_version {
+            sdk.jsonrpc_version = version;
+        }
+        if let Some(capacity) = self.label_index_capacity {
+            sdk.labels = LabelIndex::new(capacity);
+        }
+        sdk.recent_submissions = self.recent_submissions_capacity.map(RecentSubmissions::new);
+
+        #[cfg(feature = "solana-rpc")]
+        {
+            sdk.solana_rpc = self
+                .solana_rpc_url
+                .map(|url| std::sync::Arc::new(RpcClient::new(url)));
+            sdk.payer_balance_reserve = self.payer_balance_reserve;
+        }
+
+        Ok(sdk)
+    }
+}
+
+fn base_url_error(url: &str) -> Option<ConfigError> {
+    if url.is_empty() {
+        Some(ConfigError::EmptyBaseUrl)
+    } else if !is_http_url(url) {
+        Some(ConfigError::InvalidBaseUrl(url.to_string()))
+    } else if url.ends_with('/') {
+        Some(ConfigError::TrailingSlash(url.to_string()))
+    } else {
+        None
+    }
+}
+
+fn is_http_url(url: &str) -> bool {
+    Url::parse(url).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+// Canonical 8-4-4-4-12 hex form, which is what the block engine issues.
+fn is_uuid(value: &str) -> bool {
+    value.len() == 36
+        && value.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+// Accepts plain or colon-separated hex, in either case.
+#[cfg(feature = "cert-pinning")]
+fn parse_fingerprint(value: &str) -> Option<[u8; 32]> {
+    let hex: Vec<u8> = value.bytes().filter(|&b| b != b':').collect();
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut digest = [0u8; 32];
+    for (byte, pair) in digest.iter_mut().zip(hex.chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    Some(digest)
+}
@@ -0,0 +1,709 @@
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use solana_hash::Hash;
+use solana_instruction::Instruction;
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use solana_signer::Signer;
+use solana_system_interface::{instruction as system_instruction, program as system_program};
+use solana_transaction::{versioned::VersionedTransaction, Transaction};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use tracing::warn;
+
+use crate::error::JitoError;
+use crate::request_body;
+use crate::status::{BundleStatusEntry, CommitmentLevel};
+use crate::tip::{dedupe_compute_budget, tip_ix};
+
+/// Maximum number of transactions the block engine accepts in one bundle.
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 5;
+
+/// Public explorer endpoint mapping a transaction signature to the landed
+/// bundle that contained it.
+pub const BUNDLE_BY_TRANSACTION_URL: &str = "https://bundles.jito.wtf/api/v1/bundles/transaction";
+
+/// Maximum serialized size of a transaction, signatures included.
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+// Programs whose instructions `verify_no_conflicting_order` understands.
+const TOKEN_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const TOKEN_2022_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+// One entry of a `BUNDLE_BY_TRANSACTION_URL` response.
+#[derive(Debug, Deserialize)]
+pub(crate) struct BundleByTransaction {
+    pub bundle_id: BundleId,
+}
+
+/// Options object sent after the transactions in `sendBundle` params. Unset
+/// optional fields are left out of the JSON, so the default serializes to
+/// `{"encoding": "base64"}`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleOptions {
+    pub encoding: Encoding,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip_preflight: Option<bool>,
+}
+
+/// Bundle UUID assigned by the block engine on submission.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BundleId(pub String);
+
+impl BundleId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for BundleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for BundleId {
+    fn from(id: String) -> Self {
+        BundleId(id)
+    }
+}
+
+impl From<&str> for BundleId {
+    fn from(id: &str) -> Self {
+        BundleId(id.to_string())
+    }
+}
+
+/// Wire encoding of serialized transactions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    Base58,
+    #[default]
+    Base64,
+}
+
+impl Encoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Encoding::Base58 => "base58",
+            Encoding::Base64 => "base64",
+        }
+    }
+
+    fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Base58 => bs58::encode(bytes).into_string(),
+            Encoding::Base64 => general_purpose::STANDARD.encode(bytes),
+        }
+    }
+
+    fn decode(&self, payload: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Encoding::Base58 => bs58::decode(payload).into_vec().map_err(|e| e.to_string()),
+            Encoding::Base64 => general_purpose::STANDARD
+                .decode(payload)
+                .map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Block explorer used when building transaction links.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Explorer {
+    #[default]
+    Solscan,
+    SolanaFm,
+    Explorer,
+}
+
+impl Explorer {
+    pub fn transaction_url(&self, signature: &str) -> String {
+        match self {
+            Explorer::Solscan => format!("https://solscan.io/tx/{}", signature),
+            Explorer::SolanaFm => format!("https://solana.fm/tx/{}", signature),
+            Explorer::Explorer => format!("https://explorer.solana.com/tx/{}", signature),
+        }
+    }
+}
+
+/// Final state of a bundle as reported by `getBundleStatuses`.
+#[derive(Debug, Clone, Default)]
+pub struct BundleOutcome {
+    pub bundle_id: BundleId,
+    /// Slot reported by `getBundleStatuses`.
+    pub slot: Option<u64>,
+    /// Slot reported as landed by `getInflightBundleStatuses`, which can
+    /// differ from `slot`.
+    pub landed_slot: Option<u64>,
+    pub confirmation_status: Option<CommitmentLevel>,
+    pub transactions: Vec<Signature>,
+}
+
+/// Result of `confirm_bundle`: the bundle's final status, with its landed
+/// slot and transaction signatures.
+pub type BundleConfirmation = BundleOutcome;
+
+impl BundleOutcome {
+    /// Fails with `JitoError::MalformedStatus` if the entry lists no
+    /// transactions, which a landed bundle always has, or a signature that
+    /// doesn't parse.
+    pub(crate) fn from_status_entry(entry: BundleStatusEntry) -> Result<Self, JitoError> {
+        let malformed = |reason: String| JitoError::MalformedStatus {
+            bundle_id: entry.bundle_id.clone(),
+            reason,
+        };
+        if entry.transactions.is_empty() {
+            return Err(malformed("landed bundle lists no transactions".to_string()));
+        }
+        let transactions = entry
+            .transactions
+            .iter()
+            .map(|signature| {
+                Signature::from_str(signature)
+                    .map_err(|e| malformed(format!("invalid signature {}: {}", signature, e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            slot: entry.slot,
+            landed_slot: entry.landed_slot,
+            confirmation_status: entry
+                .confirmation_status
+                .as_deref()
+                .and_then(CommitmentLevel::parse),
+            transactions,
+            bundle_id: entry.bundle_id,
+        })
+    }
+
+    /// Slots between `slot` and `landed_slot`, positive when the bundle
+    /// landed later than the reported processing slot. `None` unless both
+    /// are known.
+    pub fn land_delay_slots(&self) -> Option<i64> {
+        Some(self.landed_slot? as i64 - self.slot? as i64)
+    }
+
+    /// Explorer links for every transaction in the bundle, in bundle order.
+    pub fn explorer_urls(&self, explorer: Explorer) -> Vec<String> {
+        self.transactions
+            .iter()
+            .map(|signature| explorer.transaction_url(&signature.to_string()))
+            .collect()
+    }
+}
+
+/// What `BundleBuilder::add_transaction` does with a transaction whose first
+/// signature is already in the bundle.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    #[default]
+    Reject,
+    Skip,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct BundleBuilder {
+    transactions: Vec<Transaction>,
+    on_duplicate: DuplicatePolicy,
+    dedupe_compute_budget: bool,
+}
+
+impl BundleBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a bundle with one transaction per instruction group, paid by
+    /// `payer` and signed against `recent_blockhash` by whichever of
+    /// `signers` each transaction requires.
+    pub fn from_instruction_groups(
+        groups: Vec<Vec<Instruction>>,
+        payer: &Pubkey,
+        signers: &[&Keypair],
+        recent_blockhash: Hash,
+    ) -> Result<Self, JitoError> {
+        if groups.len() > MAX_BUNDLE_TRANSACTIONS {
+            return Err(JitoError::InvalidBundle(format!(
+                "Bundle can contain at most {} transactions",
+                MAX_BUNDLE_TRANSACTIONS
+            )));
+        }
+
+        let mut transactions: Vec<Transaction> = groups
+            .iter()
+            .map(|instructions| Transaction::new_with_payer(instructions, Some(payer)))
+            .collect();
+        sign_bundle_transactions(&mut transactions, signers, recent_blockhash)?;
+
+        let mut bundle = Self::new();
+        for transaction in transactions {
+            bundle.add_transaction(transaction)?;
+        }
+        Ok(bundle)
+    }
+
+    pub fn on_duplicate(mut self, policy: DuplicatePolicy) -> Self {
+        self.on_duplicate = policy;
+        self
+    }
+
+    /// Strips repeated Compute Budget instructions from transactions as they
+    /// are added, as `dedupe_compute_budget` does. An unsigned transaction
+    /// is fixed in place; a signed one is refused with
+    /// `JitoError::InvalidBundle`, since fixing it would void its
+    /// signatures. Off by default.
+    pub fn dedupe_compute_budget(mut self, enabled: bool) -> Self {
+        self.dedupe_compute_budget = enabled;
+        self
+    }
+
+    /// Appends a signed transaction. A transaction whose first signature is
+    /// already present would be rejected by the engine, so it is either
+    /// refused with `JitoError::DuplicateTransaction` or skipped with a
+    /// warning, depending on the configured `DuplicatePolicy`.
+    pub fn add_transaction(
+        &mut self,
+        mut transaction: Transaction,
+    ) -> Result<&mut Self, JitoError> {
+        if self.dedupe_compute_budget && dedupe_compute_budget(&mut transaction) {
+            if first_signature(&transaction).is_some() {
+                return Err(JitoError::InvalidBundle(format!(
+                    "transaction {} repeats compute budget instructions; dedupe before signing",
+                    self.transactions.len()
+                )));
+            }
+            warn!("Removed repeated compute budget instructions from transaction");
+        }
+
+        if let Some(signature) = first_signature(&transaction) {
+            let duplicate = self
+                .transactions
+                .iter()
+                .any(|tx| first_signature(tx) == Some(signature));
+            if duplicate {
+                match self.on_duplicate {
+                    DuplicatePolicy::Reject => {
+                        return Err(JitoError::DuplicateTransaction {
+                            signature: signature.to_string(),
+                        })
+                    }
+                    DuplicatePolicy::Skip => {
+                        warn!("Skipping duplicate transaction {} in bundle", signature);
+                        return Ok(self);
+                    }
+                }
+            }
+        }
+
+        self.transactions.push(transaction);
+        Ok(self)
+    }
+
+    pub fn transactions(&self) -> &[Transaction] {
+        &self.transactions
+    }
+
+    /// Best-effort check that no transaction depends on an account in a way
+    /// the bundle's order breaks: using an account an earlier transaction
+    /// closed with a token `CloseAccount`, or using one before the
+    /// transaction that creates it. Only those instructions are recognized,
+    /// so an empty result doesn't prove the order is safe. Advisory only:
+    /// nothing prevents building or sending the bundle.
+    pub fn verify_no_conflicting_order(&self) -> Vec<OrderingWarning> {
+        let mut warnings = Vec::new();
+        let mut first_use: HashMap<Pubkey, usize> = HashMap::new();
+        let mut closed: HashMap<Pubkey, usize> = HashMap::new();
+
+        for (index, transaction) in self.transactions.iter().enumerate() {
+            let lifecycle = AccountLifecycle::of(transaction);
+            let keys = &transaction.message.account_keys;
+
+            for account in keys {
+                if let Some(&closed_in) = closed.get(account) {
+                    if !lifecycle.created.contains(account) {
+                        warnings.push(OrderingWarning::UseAfterClose {
+                            account: *account,
+                            closed_in,
+                            used_in: index,
+                        });
+                    }
+                }
+            }
+            for account in &lifecycle.created {
+                // Recreating an account closed earlier is a legitimate reuse.
+                if closed.remove(account).is_some() {
+                    continue;
+                }
+                if let Some(&used_in) = first_use.get(account) {
+                    warnings.push(OrderingWarning::UseBeforeCreate {
+                        account: *account,
+                        created_in: index,
+                        used_in,
+                    });
+                }
+            }
+            for account in lifecycle.closed {
+                closed.insert(account, index);
+            }
+            for account in keys {
+                first_use.entry(*account).or_insert(index);
+            }
+        }
+        warnings
+    }
+
+    /// Serializes the bundle into the params expected by `send_bundle`.
+    pub fn build(&self) -> Result<Value, JitoError> {
+        if self.transactions.is_empty() {
+            return Err(JitoError::InvalidBundle(
+                "Bundle must contain at least one transaction".to_string(),
+            ));
+        }
+        if self.transactions.len() > MAX_BUNDLE_TRANSACTIONS {
+            return Err(JitoError::InvalidBundle(format!(
+                "Bundle can contain at most {} transactions",
+                MAX_BUNDLE_TRANSACTIONS
+            )));
+        }
+
+        let encoded = self
+            .transactions
+            .iter()
+            .map(|tx| Ok(general_purpose::STANDARD.encode(bincode::serialize(tx)?)))
+            .collect::<Result<Vec<_>, JitoError>>()?;
+
+        Ok(json!([encoded, BundleOptions::default()]))
+    }
+
+    /// The complete `sendBundle` JSON-RPC request body for this bundle, as the
+    /// bytes that would be sent on the wire.
+    pub fn body_bytes(&self) -> Result<Vec<u8>, JitoError> {
+        request_body("sendBundle", self.build()?)
+    }
+
+    /// Serializes the bundle ahead of time for `send_bundle_prepared`.
+    pub fn prepare(&self) -> Result<PreparedBundle, JitoError> {
+        PreparedBundle::from_params(self.build()?)
+    }
+}
+
+/// Ordering hazard found by `BundleBuilder::verify_no_conflicting_order`.
+/// Indices are positions of transactions in the bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderingWarning {
+    /// `account` is used after an earlier transaction closed it.
+    UseAfterClose {
+        account: Pubkey,
+        closed_in: usize,
+        used_in: usize,
+    },
+    /// `account` is used before the transaction that creates it.
+    UseBeforeCreate {
+        account: Pubkey,
+        created_in: usize,
+        used_in: usize,
+    },
+}
+
+impl fmt::Display for OrderingWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderingWarning::UseAfterClose {
+                account,
+                closed_in,
+                used_in,
+            } => write!(
+                f,
+                "transaction {} uses {}, closed by transaction {}",
+                used_in, account, closed_in
+            ),
+            OrderingWarning::UseBeforeCreate {
+                account,
+                created_in,
+                used_in,
+            } => write!(
+                f,
+                "transaction {} uses {}, created later by transaction {}",
+                used_in, account, created_in
+            ),
+        }
+    }
+}
+
+// Accounts a transaction creates or closes, as far as its instructions are
+// recognized: system `CreateAccount` and `CreateAccountWithSeed`, associated
+// token account creation, and token `CloseAccount`.
+#[derive(Default)]
+struct AccountLifecycle {
+    created: HashSet<Pubkey>,
+    closed: HashSet<Pubkey>,
+}
+
+impl AccountLifecycle {
+    fn of(transaction: &Transaction) -> Self {
+        let keys = &transaction.message.account_keys;
+        let mut lifecycle = Self::default();
+
+        for ix in &transaction.message.instructions {
+            let Some(program) = keys.get(ix.program_id_index as usize) else {
+                continue;
+            };
+            let account = |position: usize| {
+                ix.accounts
+                    .get(position)
+                    .and_then(|&i| keys.get(i as usize))
+                    .copied()
+            };
+            let tag = ix.data.first().copied();
+
+            let created = match tag {
+                Some(0 | 3) if *program == system_program::ID => account(1),
+                // `Create` has empty data or tag 0, `CreateIdempotent` tag 1.
+                None | Some(0 | 1) if *program == ASSOCIATED_TOKEN_PROGRAM_ID => account(1),
+                _ => None,
+            };
+            let closed = match tag {
+                Some(9) if *program == TOKEN_PROGRAM_ID || *program == TOKEN_2022_PROGRAM_ID => {
+                    account(0)
+                }
+                _ => None,
+            };
+            lifecycle.created.extend(created);
+            lifecycle.closed.extend(closed);
+        }
+        lifecycle
+    }
+}
+
+/// A bundle serialized ahead of submission: build it off the hot path with
+/// `BundleBuilder::prepare`, then submit it later with
+/// `send_bundle_prepared`, which sends the stored request body as is.
+#[derive(Debug, Clone)]
+pub struct PreparedBundle {
+    params: Value,
+    body: Vec<u8>,
+}
+
+impl PreparedBundle {
+    /// Wraps `sendBundle` params built elsewhere, checking they are still
+    /// `[[tx, ...], {options}]` with 1 to `MAX_BUNDLE_TRANSACTIONS` encoded
+    /// transactions.
+    pub fn from_params(params: Value) -> Result<Self, JitoError> {
+        let transactions = match params.as_array().map(Vec::as_slice) {
+            Some([Value::Array(transactions), Value::Object(_)]) => transactions,
+            _ => {
+                return Err(JitoError::InvalidBundle(
+                    "params must be [transactions, options]".to_string(),
+                ))
+            }
+        };
+        if transactions.is_empty() || transactions.len() > MAX_BUNDLE_TRANSACTIONS {
+            return Err(JitoError::InvalidBundle(format!(
+                "Bundle must contain 1 to {} transactions",
+                MAX_BUNDLE_TRANSACTIONS
+            )));
+        }
+        if !transactions.iter().all(Value::is_string) {
+            return Err(JitoError::InvalidBundle(
+                "transactions must be encoded strings".to_string(),
+            ));
+        }
+
+        let body = request_body("sendBundle", params.clone())?;
+        Ok(Self { params, body })
+    }
+
+    pub fn params(&self) -> &Value {
+        &self.params
+    }
+
+    // Request body, serialized with the default `jsonrpc` version.
+    pub(crate) fn body(&self) -> &[u8] {
+        &self.body
+    }
+}
+
+/// Signs every transaction of a bundle with `recent_blockhash`. Each
+/// transaction is signed by the subset of `signers` its message requires, and
+/// `JitoError::MissingSigner` is returned if any required signer is absent.
+pub fn sign_bundle_transactions(
+    transactions: &mut [Transaction],
+    signers: &[&Keypair],
+    recent_blockhash: Hash,
+) -> Result<(), JitoError> {
+    for (index, transaction) in transactions.iter_mut().enumerate() {
+        let message = &transaction.message;
+        let required = &message.account_keys[..message.header.num_required_signatures as usize];
+
+        let tx_signers = required
+            .iter()
+            .map(|pubkey| {
+                signers
+                    .iter()
+                    .find(|signer| signer.pubkey() == *pubkey)
+                    .copied()
+                    .ok_or_else(|| JitoError::MissingSigner {
+                        index,
+                        pubkey: pubkey.to_string(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        transaction.try_sign(&tx_signers, recent_blockhash)?;
+    }
+    Ok(())
+}
+
+/// A plain SOL transfer for `transfer_transactions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferSpec {
+    pub to: Pubkey,
+    pub lamports: u64,
+}
+
+/// Builds unsigned transactions paying `transfers` from `from`, packing as
+/// many transfers into each transaction as fit in `MAX_TRANSACTION_SIZE`.
+/// The tip to `tip_account` is appended to the last transaction. Fails with
+/// `JitoError::InvalidBundle` if there are no transfers or they don't fit in
+/// `MAX_BUNDLE_TRANSACTIONS` transactions.
+pub fn transfer_transactions(
+    from: &Pubkey,
+    transfers: &[TransferSpec],
+    tip_account: &Pubkey,
+    tip_lamports: u64,
+) -> Result<Vec<Transaction>, JitoError> {
+    if transfers.is_empty() {
+        return Err(JitoError::InvalidBundle(
+            "At least one transfer is required".to_string(),
+        ));
+    }
+
+    let tip = tip_ix(from, tip_account, tip_lamports);
+    let mut groups: Vec<Vec<Instruction>> = vec![Vec::new()];
+    for transfer in transfers {
+        let ix = system_instruction::transfer(from, &transfer.to, transfer.lamports);
+        let current = groups.last_mut().unwrap();
+        current.push(ix);
+        // Every transaction is sized as if it carried the tip, since any of
+        // them may end up last.
+        let with_tip = [current.as_slice(), std::slice::from_ref(&tip)].concat();
+        if transaction_size(&with_tip, from)? > MAX_TRANSACTION_SIZE {
+            let ix = current.pop().unwrap();
+            if current.is_empty() {
+                return Err(JitoError::InvalidBundle(
+                    "A single transfer exceeds the transaction size limit".to_string(),
+                ));
+            }
+            groups.push(vec![ix]);
+        }
+    }
+
+    if groups.len() > MAX_BUNDLE_TRANSACTIONS {
+        return Err(JitoError::InvalidBundle(format!(
+            "{} transfers need {} transactions, more than the {} a bundle allows",
+            transfers.len(),
+            groups.len(),
+            MAX_BUNDLE_TRANSACTIONS
+        )));
+    }
+
+    groups.last_mut().unwrap().push(tip);
+    Ok(groups
+        .iter()
+        .map(|instructions| Transaction::new_with_payer(instructions, Some(from)))
+        .collect())
+}
+
+// Unsigned transactions already carry placeholder signatures, so this is the
+// size once signed.
+fn transaction_size(instructions: &[Instruction], payer: &Pubkey) -> Result<usize, JitoError> {
+    let transaction = Transaction::new_with_payer(instructions, Some(payer));
+    Ok(bincode::serialized_size(&transaction)? as usize)
+}
+
+/// Serializes signed transactions into `send_bundle` / `send_txn` payloads.
+/// v0 messages keep their address table lookups, so the engine resolves the
+/// same accounts the transaction was signed over. Fails with
+/// `JitoError::InvalidTransactionPayload` for a transaction larger than
+/// `MAX_TRANSACTION_SIZE`.
+pub fn encode_versioned_transactions(
+    transactions: &[VersionedTransaction],
+    encoding: Encoding,
+) -> Result<Vec<String>, JitoError> {
+    transactions
+        .iter()
+        .enumerate()
+        .map(|(index, transaction)| {
+            let bytes = bincode::serialize(transaction)?;
+            if bytes.len() > MAX_TRANSACTION_SIZE {
+                return Err(JitoError::InvalidTransactionPayload {
+                    index,
+                    reason: format!(
+                        "{} bytes exceeds the {} byte limit",
+                        bytes.len(),
+                        MAX_TRANSACTION_SIZE
+                    ),
+                });
+            }
+            Ok(encoding.encode(&bytes))
+        })
+        .collect()
+}
+
+/// Checks that every payload decodes with `encoding` and deserializes into a
+/// transaction, so malformed input is caught before it reaches the engine.
+/// Legacy and versioned transactions are both accepted. The error carries the
+/// index of the first payload that fails.
+pub fn validate_encoded_transactions(
+    encoded: &[String],
+    encoding: Encoding,
+) -> Result<(), JitoError> {
+    for (index, payload) in encoded.iter().enumerate() {
+        let bytes = encoding
+            .decode(payload)
+            .map_err(|reason| JitoError::InvalidTransactionPayload { index, reason })?;
+        bincode::deserialize::<VersionedTransaction>(&bytes).map_err(|e| {
+            JitoError::InvalidTransactionPayload {
+                index,
+                reason: e.to_string(),
+            }
+        })?;
+    }
+    Ok(())
+}
+
+/// Transactions of `sendBundle` params (`[transactions, options]`),
+/// decoded with the encoding the options name. Payloads that don't decode
+/// are skipped and left for the engine to reject.
+pub(crate) fn decode_bundle_params(params: &Value) -> Vec<VersionedTransaction> {
+    let encoding = match params[1]["encoding"].as_str() {
+        Some("base58") => Encoding::Base58,
+        _ => Encoding::Base64,
+    };
+    params[0]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter_map(|payload| encoding.decode(payload).ok())
+        .filter_map(|bytes| bincode::deserialize::<VersionedTransaction>(&bytes).ok())
+        .collect()
+}
+
+// Unsigned transactions carry a default signature, which would make every
+// unsigned transaction look like a duplicate of the others.
+fn first_signature(transaction: &Transaction) -> Option<&Signature> {
+    transaction
+        .signatures
+        .first()
+        .filter(|signature| **signature != Signature::default())
+}
@@ -0,0 +1,169 @@
+use rustls::client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier};
+use rustls::{
+    Certificate, CertificateError, ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName,
+};
+use sha2::{Digest, Sha256};
+use std::error::Error as StdError;
+use std::fmt;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Server certificate that matched none of the pinned fingerprints. Fails
+/// the TLS handshake, before anything is sent, and surfaces as
+/// `JitoError::CertificatePinMismatch`.
+#[derive(Debug)]
+pub(crate) struct PinMismatch {
+    pub expected: Vec<[u8; 32]>,
+    pub actual: [u8; 32],
+}
+
+impl fmt::Display for PinMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "certificate SHA-256 {} matches no pinned fingerprint",
+            hex(&self.actual)
+        )
+    }
+}
+
+impl StdError for PinMismatch {}
+
+impl PinMismatch {
+    pub fn expected_hex(&self) -> String {
+        let expected: Vec<String> = self.expected.iter().map(hex).collect();
+        expected.join(", ")
+    }
+
+    pub fn actual_hex(&self) -> String {
+        hex(&self.actual)
+    }
+
+    /// The mismatch behind a failed request, if that is why it failed.
+    /// rustls errors reach reqwest wrapped in an `io::Error`, whose `source`
+    /// skips the wrapped error, so those are unwrapped explicitly.
+    pub fn find<'a>(err: &'a (dyn StdError + 'static)) -> Option<&'a PinMismatch> {
+        let mut source = Some(err);
+        while let Some(cause) = source {
+            if let Some(rustls::Error::InvalidCertificate(CertificateError::Other(other))) =
+                cause.downcast_ref::<rustls::Error>()
+            {
+                if let Some(mismatch) = other.downcast_ref::<PinMismatch>() {
+                    return Some(mismatch);
+                }
+            }
+            source = match cause.downcast_ref::<std::io::Error>() {
+                Some(io) => io.get_ref().map(|inner| inner as &(dyn StdError + 'static)),
+                None => cause.source(),
+            };
+        }
+        None
+    }
+}
+
+// Checks the pin first, so a wrong certificate is reported as a pin
+// mismatch whatever its chain, then verifies the chain against the webpki
+// roots as an unpinned connection would.
+struct PinnedCertVerifier {
+    pins: Vec<[u8; 32]>,
+    webpki: WebPkiVerifier,
+}
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        check_pin(&self.pins, end_entity)?;
+        self.webpki.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            scts,
+            ocsp_response,
+            now,
+        )
+    }
+}
+
+fn check_pin(pins: &[[u8; 32]], certificate: &Certificate) -> Result<(), rustls::Error> {
+    let actual: [u8; 32] = Sha256::digest(&certificate.0).into();
+    if pins.contains(&actual) {
+        return Ok(());
+    }
+    Err(rustls::Error::InvalidCertificate(CertificateError::Other(
+        Arc::new(PinMismatch {
+            expected: pins.to_vec(),
+            actual,
+        }),
+    )))
+}
+
+/// rustls configuration for `ClientBuilder::use_preconfigured_tls` that only
+/// completes handshakes with a server whose certificate matches one of
+/// `pins`.
+pub(crate) fn pinned_tls_config(pins: &[[u8; 32]]) -> ClientConfig {
+    let mut roots = RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(
+            anchor.subject,
+            anchor.spki,
+            anchor.name_constraints,
+        )
+    }));
+    let verifier = PinnedCertVerifier {
+        pins: pins.to_vec(),
+        webpki: WebPkiVerifier::new(roots, None),
+    };
+
+    let mut config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(Arc::new(verifier))
+        .with_no_client_auth();
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    config
+}
+
+fn hex(digest: &[u8; 32]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest(der: &[u8]) -> [u8; 32] {
+        Sha256::digest(der).into()
+    }
+
+    #[test]
+    fn certificate_matching_any_pin_passes() {
+        let certificate = Certificate(b"block engine".to_vec());
+        let pins = [digest(b"previous"), digest(b"block engine")];
+
+        assert!(check_pin(&pins, &certificate).is_ok());
+    }
+
+    #[test]
+    fn mismatch_reports_expected_and_actual_fingerprints() {
+        let certificate = Certificate(b"impostor".to_vec());
+        let err = check_pin(&[digest(b"block engine")], &certificate).unwrap_err();
+
+        let mismatch = PinMismatch::find(&err).expect("a pin mismatch");
+        assert_eq!(mismatch.actual, digest(b"impostor"));
+        assert_eq!(mismatch.expected_hex(), hex(&digest(b"block engine")));
+    }
+
+    #[test]
+    fn mismatch_is_found_inside_an_io_error() {
+        let err = check_pin(&[[0; 32]], &Certificate(b"impostor".to_vec())).unwrap_err();
+        let io = std::io::Error::new(std::io::ErrorKind::InvalidData, err);
+
+        assert!(PinMismatch::find(&io).is_some());
+    }
+}
@@ -0,0 +1,149 @@
+use serde_json::Value;
+use std::error::Error as StdError;
+use std::time::Duration;
+
+use crate::bundle::BundleId;
+use crate::response::RpcErrorData;
+use crate::status::InflightBundleStatus;
+
+#[derive(Debug, thiserror::Error)]
+pub enum JitoError {
+    #[error("Failed to resolve block engine host {host}")]
+    DnsResolution {
+        host: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("Request error: {0}")]
+    Http(reqwest::Error),
+    #[error("Request timed out after {elapsed:?} (limit {limit:?})")]
+    Timeout { elapsed: Duration, limit: Duration },
+    #[error("Block engine certificate {actual:?} does not match pinned SHA-256 {expected}")]
+    CertificatePinMismatch {
+        expected: String,
+        actual: Option<String>,
+    },
+    #[error("JSON-RPC error {code}: {message}")]
+    RpcError {
+        code: i64,
+        message: String,
+        /// The error's `data` member, kept as sent. See `rpc_error_data` for
+        /// the typed view.
+        data: Option<Value>,
+    },
+    #[error("Malformed response: {0}")]
+    MalformedResponse(String),
+    #[error("Expected a JSON response but got {content_type}: {snippet}")]
+    UnexpectedResponse {
+        content_type: String,
+        snippet: String,
+    },
+    #[error("Bundle {bundle_id} failed: {reason}")]
+    BundleFailed { bundle_id: BundleId, reason: String },
+    #[error("Bundle {bundle_id} was dropped by the engine without landing")]
+    BundleDropped { bundle_id: BundleId },
+    #[error("Malformed status for bundle {bundle_id}: {reason}")]
+    MalformedStatus { bundle_id: BundleId, reason: String },
+    #[error("Operation deadline exceeded during {step}")]
+    DeadlineExceeded { step: &'static str },
+    /// `last_status` is the last in-flight status seen, `None` if the engine
+    /// never reported one; the bundle may still land.
+    #[error("Bundle not confirmed after {attempts} attempts in {elapsed:?} (last in-flight status: {last_status:?})")]
+    ConfirmationTimeout {
+        last_status: Option<InflightBundleStatus>,
+        attempts: u32,
+        elapsed: Duration,
+    },
+    #[error("Payer has {available} lamports but the bundle needs {required}")]
+    InsufficientFunds { required: u64, available: u64 },
+    #[error("No Solana RPC configured")]
+    SolanaRpcNotConfigured,
+    #[cfg(feature = "solana-rpc")]
+    #[error("Solana RPC error: {0}")]
+    SolanaRpc(Box<solana_client::client_error::ClientError>),
+    #[error("Cannot split tip: {0}")]
+    InvalidTipSplit(String),
+    #[error("Tip account {value} is not a valid pubkey")]
+    InvalidTipAccount { value: String },
+    #[error("No tip amount given and no default tip configured")]
+    NoTipAmount,
+    #[error("None of the fetched tip accounts are on the tip account allowlist")]
+    NoAllowedTipAccounts,
+    #[error("Bundle-only transaction pays no tip to a tip account")]
+    MissingTip,
+    #[error("Bundle tips {requested} lamports, above the {cap} lamport cap")]
+    TipTooHigh { requested: u64, cap: u64 },
+    #[error("Bundle simulation failed: {reason}")]
+    SimulationFailed { reason: String, logs: Vec<String> },
+    #[error("Invalid bundle: {0}")]
+    InvalidBundle(String),
+    #[error("No regions to submit to")]
+    NoRegions,
+    #[error("Transaction payload {index} is invalid: {reason}")]
+    InvalidTransactionPayload { index: usize, reason: String },
+    #[error("Transaction {signature} is already in the bundle")]
+    DuplicateTransaction { signature: String },
+    #[error("Transaction {index} requires a signature from {pubkey}")]
+    MissingSigner { index: usize, pubkey: String },
+    #[error("Failed to sign transaction: {0}")]
+    Signing(#[from] solana_signer::SignerError),
+    #[error("Failed to serialize transaction: {0}")]
+    Serialization(#[from] bincode::Error),
+    #[error("Failed to encode request body: {0}")]
+    Encoding(#[from] serde_json::Error),
+}
+
+#[cfg(feature = "solana-rpc")]
+impl From<solana_client::client_error::ClientError> for JitoError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        JitoError::SolanaRpc(Box::new(err))
+    }
+}
+
+impl JitoError {
+    /// Typed fields of a `RpcError`'s `data`, when it has a recognized
+    /// shape.
+    pub fn rpc_error_data(&self) -> Option<RpcErrorData> {
+        match self {
+            JitoError::RpcError {
+                data: Some(data), ..
+            } => RpcErrorData::parse(data),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for JitoError {
+    fn from(err: reqwest::Error) -> Self {
+        #[cfg(feature = "cert-pinning")]
+        if let Some(mismatch) = crate::cert_pin::PinMismatch::find(&err) {
+            return JitoError::CertificatePinMismatch {
+                expected: mismatch.expected_hex(),
+                actual: Some(mismatch.actual_hex()),
+            };
+        }
+        if err.is_connect() && is_dns_failure(&err) {
+            let host = err
+                .url()
+                .and_then(|url| url.host_str())
+                .unwrap_or_default()
+                .to_string();
+            return JitoError::DnsResolution { host, source: err };
+        }
+        JitoError::Http(err)
+    }
+}
+
+/// reqwest doesn't expose a dedicated DNS error kind, so walk the source chain
+/// and look for the resolver failure reported by hyper's connector.
+fn is_dns_failure(err: &reqwest::Error) -> bool {
+    let mut source = err.source();
+    while let Some(cause) = source {
+        let message = cause.to_string();
+        if message.contains("dns error") || message.contains("failed to lookup address") {
+            return true;
+        }
+        source = cause.source();
+    }
+    false
+}
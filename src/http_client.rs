@@ -1,15 +1,28 @@
 use anyhow::{Error, Result};
-use rand::prelude::SliceRandom;
+use hyper::client::connect::dns::Name;
 use rand::Rng;
-use reqwest::Client;
-use std::net::IpAddr;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use reqwest::{Client, ClientBuilder, RequestBuilder};
+use std::collections::HashMap;
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tracing::debug;
 
+/// Tracing target used for per-request IP selection logs.
+pub const IP_SELECT_LOG_TARGET: &str = "jito::ip_select";
+
 #[derive(Debug, thiserror::Error)]
 pub enum HttpClientError {
     #[error("Failed to bind IP {0}: {1}")]
     BindFailed(IpAddr, Error),
+    #[error("No healthy IPs available in the pool")]
+    NoHealthyIps,
+    #[error("Failed to build HTTP client: {0}")]
+    ClientBuild(reqwest::Error),
 }
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -19,37 +32,218 @@ pub enum IpSelectAlgorithm {
     Random,
 }
 
+/// Order in which fallback endpoints are tried after the endpoint that last
+/// served a request fails.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FailoverOrder {
+    /// In the configured order, wrapping around to the primary.
+    #[default]
+    Sequential,
+    /// Shuffled per request, spreading failover load across endpoints.
+    Randomized,
+}
+
+/// Delay before the connector starts connecting over the fallback address
+/// family while the preferred one is still connecting (happy eyeballs). Fixed
+/// by reqwest's connector.
+pub const HAPPY_EYEBALLS_DELAY: Duration = Duration::from_millis(300);
+
+/// Address family connected to first when the block engine host resolves to
+/// both IPv4 and IPv6 addresses. The other family remains the fallback: if
+/// the preferred family hasn't connected within `HAPPY_EYEBALLS_DELAY`, both
+/// are attempted in parallel and the first connection wins. Addresses of one
+/// family are tried one after another.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DualStackPreference {
+    /// Keep the order returned by the system resolver.
+    #[default]
+    System,
+    PreferIpv4,
+    PreferIpv6,
+}
+
+impl DualStackPreference {
+    pub fn apply(self, builder: ClientBuilder) -> ClientBuilder {
+        match self {
+            DualStackPreference::System => builder,
+            preference => builder.dns_resolver(Arc::new(PreferringResolver(preference))),
+        }
+    }
+}
+
+/// Limit for a whole request, from connecting until the response body is
+/// read, unless configured otherwise.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Settings applied to every reqwest client the SDK builds.
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    pub dual_stack_preference: DualStackPreference,
+    /// SHA-256 fingerprints of the certificates a server may present. When
+    /// set, every handshake is checked against them; see
+    /// `JitoSdkBuilder::pin_cert_sha256`.
+    #[cfg(feature = "cert-pinning")]
+    pub cert_pins: Vec<[u8; 32]>,
+    /// Limit for establishing a connection, shared evenly by the addresses
+    /// of each family, so an unresponsive address only holds up its share.
+    pub connect_timeout: Option<Duration>,
+    /// Limit for a whole request, so a hung connection can't stall a caller
+    /// forever. A timeout set on an individual request replaces it.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            dual_stack_preference: DualStackPreference::default(),
+            #[cfg(feature = "cert-pinning")]
+            cert_pins: Vec::new(),
+            connect_timeout: None,
+            timeout: Some(DEFAULT_REQUEST_TIMEOUT),
+        }
+    }
+}
+
+impl ClientOptions {
+    pub fn apply(&self, builder: ClientBuilder) -> ClientBuilder {
+        let mut builder = self.dual_stack_preference.apply(builder);
+        #[cfg(feature = "cert-pinning")]
+        if !self.cert_pins.is_empty() {
+            builder =
+                builder.use_preconfigured_tls(crate::cert_pin::pinned_tls_config(&self.cert_pins));
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        builder
+    }
+}
+
+/// Client with the default `ClientOptions`, for SDKs without an IP pool.
+pub(crate) fn default_client() -> Client {
+    ClientOptions::default()
+        .apply(Client::builder())
+        .build()
+        .expect("the default client configuration is valid")
+}
+
+// System resolver whose results are reordered so the preferred family comes
+// first; the connector tries addresses in that order.
+struct PreferringResolver(DualStackPreference);
+
+impl Resolve for PreferringResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let prefer_ipv6 = self.0 == DualStackPreference::PreferIpv6;
+        Box::pin(async move {
+            let mut addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            addrs.sort_by_key(|addr| addr.is_ipv6() != prefer_ipv6);
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct HttpClient {
     clients: Arc<Vec<Client>>,
     algorithm: IpSelectAlgorithm,
     round_robin_index: Arc<Mutex<usize>>,
     last_random_ip: Arc<Mutex<Option<usize>>>,
+    healthy: Arc<Vec<AtomicBool>>,
 }
 
 impl HttpClient {
-    pub fn new(ips: Vec<IpAddr>, algorithm: IpSelectAlgorithm) -> Result<Self, HttpClientError> {
+    /// Pool of clients bound to `ips` whose requests fail with
+    /// `JitoError::Timeout` after `timeout`.
+    pub fn new(
+        ips: Vec<IpAddr>,
+        algorithm: IpSelectAlgorithm,
+        timeout: Duration,
+    ) -> Result<Self, HttpClientError> {
+        let options = ClientOptions {
+            timeout: Some(timeout),
+            ..ClientOptions::default()
+        };
+        Self::new_with_options(ips, algorithm, options)
+    }
+
+    pub fn new_with_options(
+        ips: Vec<IpAddr>,
+        algorithm: IpSelectAlgorithm,
+        options: ClientOptions,
+    ) -> Result<Self, HttpClientError> {
         let clients = if ips.is_empty() {
-            vec![Client::new()]
+            vec![options
+                .apply(Client::builder())
+                .build()
+                .map_err(HttpClientError::ClientBuild)?]
         } else {
             ips.iter()
                 .map(|&ip| {
-                    Client::builder()
-                        .local_address(Some(ip))
+                    options
+                        .apply(Client::builder().local_address(Some(ip)))
                         .build()
                         .map_err(|e| HttpClientError::BindFailed(ip, e.into()))
                 })
                 .collect::<Result<Vec<_>, _>>()?
         };
 
-        Ok(Self {
+        Ok(Self::from_clients(clients, algorithm))
+    }
+
+    /// Builds a pool from already-configured clients, e.g. to exercise the
+    /// selection logic in tests without binding local addresses. An empty
+    /// list falls back to a single default client.
+    pub fn from_clients(clients: Vec<Client>, algorithm: IpSelectAlgorithm) -> Self {
+        let clients = if clients.is_empty() {
+            vec![default_client()]
+        } else {
+            clients
+        };
+
+        let healthy = clients.iter().map(|_| AtomicBool::new(true)).collect();
+
+        Self {
             clients: Arc::new(clients),
             algorithm,
             round_robin_index: Arc::new(Mutex::new(0)),
             last_random_ip: Arc::new(Mutex::new(None)),
-        })
+            healthy: Arc::new(healthy),
+        }
+    }
+
+    pub fn clients_len(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Excludes the IP at `index` from selection until it is marked healthy
+    /// again.
+    pub fn mark_unhealthy(&self, index: usize) {
+        if let Some(healthy) = self.healthy.get(index) {
+            healthy.store(false, Ordering::Relaxed);
+        }
+    }
+
+    pub fn mark_healthy(&self, index: usize) {
+        if let Some(healthy) = self.healthy.get(index) {
+            healthy.store(true, Ordering::Relaxed);
+        }
+    }
+
+    pub fn healthy_count(&self) -> usize {
+        self.healthy
+            .iter()
+            .filter(|healthy| healthy.load(Ordering::Relaxed))
+            .count()
     }
 
+    /// Selects a client among the healthy IPs. When every IP is marked
+    /// unhealthy this still returns one; use `try_get_client` to back off
+    /// instead.
     pub fn get_client(&self) -> Client {
         match self.clients.len() {
             0 => unreachable!(),
@@ -58,32 +252,383 @@ impl HttpClient {
         }
     }
 
-    /// 多IP选择算法
+    /// Like `get_client`, but fails with `HttpClientError::NoHealthyIps`
+    /// rather than handing out a client bound to an IP marked unhealthy.
+    pub fn try_get_client(&self) -> Result<Client, HttpClientError> {
+        if self.healthy_count() == 0 {
+            return Err(HttpClientError::NoHealthyIps);
+        }
+        Ok(self.get_client())
+    }
+
+    /// Restarts selection from a known state: round-robin resumes at the
+    /// first IP and random selection forgets the last IP it picked.
+    pub fn reset_selection(&self) {
+        *self.round_robin_index.lock().unwrap() = 0;
+        *self.last_random_ip.lock().unwrap() = None;
+    }
+
+    // With no healthy IP left every IP is usable again, so `get_client`
+    // degrades to its behavior without health tracking.
+    fn is_usable(&self, index: usize, any_healthy: bool) -> bool {
+        !any_healthy || self.healthy[index].load(Ordering::Relaxed)
+    }
+
     fn select_client(&self) -> Client {
+        self.clients[self.select_index()].clone()
+    }
+
+    /// 多IP选择算法
+    fn select_index(&self) -> usize {
+        let len = self.clients.len();
+        let any_healthy = self.healthy_count() > 0;
         let index = match self.algorithm {
             IpSelectAlgorithm::RoundRobin => {
                 let mut idx = self.round_robin_index.lock().unwrap();
-                let selected = *idx;
-                *idx = (*idx + 1) % self.clients.len();
+                let selected = (0..len)
+                    .map(|offset| (*idx + offset) % len)
+                    .find(|&i| self.is_usable(i, any_healthy))
+                    .unwrap_or(*idx);
+                *idx = (selected + 1) % len;
                 selected
             }
             IpSelectAlgorithm::Random => {
+                // Pools up to this size are scanned; larger ones reroll. In a
+                // small pool the last pick is a large share of the draws,
+                // half of them with 2 IPs, so rerolls would miss often, while
+                // scanning a few entries is cheap. Above it, with every IP
+                // healthy a draw repeats the last pick less than 1 time in 8,
+                // so 8 rerolls all miss about once in 43 million selections.
+                // `benches/ip_selection.rs` compares both paths with the
+                // allocating selection they replaced.
+                const SCAN_MAX_POOL: usize = 8;
+                const REROLLS: usize = 8;
+
                 let mut last_idx = self.last_random_ip.lock().unwrap();
-                let candidates: Vec<usize> = (0..self.clients.len())
-                    .filter(|&i| Some(i) != *last_idx)
-                    .collect();
+                let is_candidate =
+                    |i: usize| Some(i) != *last_idx && self.is_usable(i, any_healthy);
+                let mut rng = rand::thread_rng();
 
-                let selected = if candidates.is_empty() {
-                    rand::thread_rng().gen_range(0..self.clients.len())
+                // Both paths pick uniformly among the candidates without
+                // allocating. Rerolls fall back to the scan when few IPs
+                // qualify and they keep missing.
+                let rerolled = if len > SCAN_MAX_POOL {
+                    (0..REROLLS)
+                        .map(|_| rng.gen_range(0..len))
+                        .find(|&i| is_candidate(i))
                 } else {
-                    *candidates.choose(&mut rand::thread_rng()).unwrap()
+                    None
+                };
+                let selected = match rerolled {
+                    Some(i) => i,
+                    None => match (0..len).filter(|&i| is_candidate(i)).count() {
+                        // Only the last IP is usable, or there is nothing to avoid
+                        0 => (*last_idx)
+                            .filter(|&i| self.is_usable(i, any_healthy))
+                            .unwrap_or_else(|| rng.gen_range(0..len)),
+                        count => {
+                            let nth = rng.gen_range(0..count);
+                            (0..len).filter(|&i| is_candidate(i)).nth(nth).unwrap()
+                        }
+                    },
                 };
 
                 *last_idx = Some(selected);
                 selected
             }
         };
-        debug!("selected ip index: {}", index);
-        self.clients[index].clone()
+        // Logged under its own target so it can be silenced independently,
+        // e.g. RUST_LOG=debug,jito::ip_select=off
+        debug!(target: IP_SELECT_LOG_TARGET, "selected ip index: {}", index);
+        index
+    }
+}
+
+/// Caps the number of concurrent requests, and therefore open connections,
+/// per block engine endpoint. Every region is served from its own base URL,
+/// so limits are tracked per base URL and a slow region can't starve others.
+#[derive(Debug, Clone)]
+pub struct ConnectionLimiter {
+    max_per_endpoint: usize,
+    endpoints: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+}
+
+impl ConnectionLimiter {
+    pub fn new(max_per_endpoint: usize) -> Self {
+        Self {
+            max_per_endpoint,
+            endpoints: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn max_per_endpoint(&self) -> usize {
+        self.max_per_endpoint
     }
-}
\ No newline at end of file
+
+    /// Waits for a free slot on `endpoint`. The slot is released when the
+    /// returned permit is dropped.
+    pub async fn acquire(&self, endpoint: &str) -> OwnedSemaphorePermit {
+        let semaphore = self
+            .endpoints
+            .lock()
+            .unwrap()
+            .entry(endpoint.to_string())
+            .or_insert_with(|| Arc::new(Semaphore::new(self.max_per_endpoint)))
+            .clone();
+
+        semaphore
+            .acquire_owned()
+            .await
+            .expect("connection limiter semaphores are never closed")
+    }
+}
+
+/// Rolling estimate of request round-trip time, smoothed like TCP's SRTT
+/// so a single slow response doesn't swing it.
+#[derive(Debug, Clone, Default)]
+pub struct RttTracker {
+    estimate: Arc<Mutex<Option<Duration>>>,
+}
+
+impl RttTracker {
+    // Weight of a new sample, as in RFC 6298.
+    const SAMPLE_WEIGHT: f64 = 0.125;
+
+    pub fn record(&self, sample: Duration) {
+        let mut estimate = self.estimate.lock().unwrap();
+        *estimate = Some(match *estimate {
+            Some(current) => {
+                current.mul_f64(1.0 - Self::SAMPLE_WEIGHT) + sample.mul_f64(Self::SAMPLE_WEIGHT)
+            }
+            None => sample,
+        });
+    }
+
+    /// Current smoothed RTT, or `None` before the first response.
+    pub fn estimate(&self) -> Option<Duration> {
+        *self.estimate.lock().unwrap()
+    }
+}
+
+/// Number of requests awaiting a response, shared across clones.
+#[derive(Debug, Clone, Default)]
+pub struct InFlightCounter {
+    count: Arc<AtomicUsize>,
+}
+
+impl InFlightCounter {
+    /// Counts a request until the returned guard is dropped, so requests
+    /// that fail or are cancelled are released too.
+    pub fn start(&self) -> InFlightGuard {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        InFlightGuard {
+            count: self.count.clone(),
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug)]
+pub struct InFlightGuard {
+    count: Arc<AtomicUsize>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.count.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// How requests are retried when the block engine answers 429 Too Many
+/// Requests. Each retry waits for the response's `Retry-After` seconds if
+/// given, otherwise `base_delay` doubled per retry with jitter, capped at
+/// `max_delay`. Once the retries run out, the 429 response is returned as
+/// usual. Other 4xx responses are never retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Never retries a 429.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    // Wait before retry number `retry` (from 0), honoring `retry_after`.
+    pub(crate) fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        let delay = retry_after.unwrap_or_else(|| {
+            let backoff = self.base_delay.saturating_mul(1 << retry.min(16));
+            rand::thread_rng().gen_range(backoff / 2..=backoff)
+        });
+        delay.min(self.max_delay)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Request timeouts, optionally overridden per JSON-RPC method so e.g.
+/// status polls can fail fast while submissions get more headroom. The
+/// default starts at `DEFAULT_REQUEST_TIMEOUT`.
+#[derive(Debug, Clone)]
+pub struct RequestTimeouts {
+    default: Option<Duration>,
+    per_method: HashMap<String, Duration>,
+}
+
+impl Default for RequestTimeouts {
+    fn default() -> Self {
+        Self {
+            default: Some(DEFAULT_REQUEST_TIMEOUT),
+            per_method: HashMap::new(),
+        }
+    }
+}
+
+impl RequestTimeouts {
+    pub fn set_default(&mut self, timeout: Duration) {
+        self.default = Some(timeout);
+    }
+
+    pub fn set_method(&mut self, method: &str, timeout: Duration) {
+        self.per_method.insert(method.to_string(), timeout);
+    }
+
+    pub fn default_timeout(&self) -> Option<Duration> {
+        self.default
+    }
+
+    pub fn per_method(&self) -> &HashMap<String, Duration> {
+        &self.per_method
+    }
+
+    /// Timeout for `method`, falling back to the default. `None` leaves the
+    /// request without a timeout.
+    pub fn for_method(&self, method: &str) -> Option<Duration> {
+        self.per_method.get(method).copied().or(self.default)
+    }
+}
+
+/// Header carrying an approved API key.
+pub const AUTH_HEADER: &str = "x-jito-auth";
+
+/// API keys rotated round-robin, one per request, to spread rate-limit quota
+/// across several approved keys. The rotation is shared by clones.
+#[derive(Debug, Clone)]
+pub struct AuthKeys {
+    keys: Arc<Vec<String>>,
+    next: Arc<AtomicUsize>,
+}
+
+impl AuthKeys {
+    /// Returns `None` for an empty key list.
+    pub fn new(keys: Vec<String>) -> Option<Self> {
+        if keys.is_empty() {
+            return None;
+        }
+        Some(Self {
+            keys: Arc::new(keys),
+            next: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    pub fn next_key(&self) -> &str {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.keys.len();
+        &self.keys[index]
+    }
+}
+
+/// Hook that can inspect or modify every outgoing request right before it is
+/// sent, e.g. to add a signature header for a private relay.
+#[derive(Clone)]
+pub struct RequestInterceptor(Arc<dyn Fn(RequestBuilder) -> RequestBuilder + Send + Sync>);
+
+impl RequestInterceptor {
+    pub fn new(f: impl Fn(RequestBuilder) -> RequestBuilder + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+
+    pub fn apply(&self, request: RequestBuilder) -> RequestBuilder {
+        (self.0)(request)
+    }
+}
+
+impl fmt::Debug for RequestInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RequestInterceptor")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(len: usize) -> HttpClient {
+        HttpClient::from_clients(vec![Client::new(); len], IpSelectAlgorithm::Random)
+    }
+
+    // Picks `draws` indices, checking that none repeats the previous one,
+    // and returns how often each was picked.
+    fn counts(client: &HttpClient, draws: usize) -> Vec<usize> {
+        let mut counts = vec![0; client.clients_len()];
+        let mut last = None;
+        for _ in 0..draws {
+            let index = client.select_index();
+            assert_ne!(Some(index), last, "picked the same IP twice in a row");
+            counts[index] += 1;
+            last = Some(index);
+        }
+        counts
+    }
+
+    fn assert_uniform(counts: &[usize], expected: usize) {
+        for (index, &count) in counts.iter().enumerate() {
+            let deviation = count.abs_diff(expected);
+            assert!(
+                deviation * 100 <= expected * 15,
+                "IP {} picked {} times, expected about {}",
+                index,
+                count,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn random_selection_is_uniform_for_scanned_and_rerolled_pools() {
+        for len in [2, 8, 9, 10, 100] {
+            let counts = counts(&pool(len), 2_000 * len);
+            assert_uniform(&counts, 2_000);
+        }
+    }
+
+    // Rerolls mostly land on unhealthy IPs here, so selection falls back to
+    // the scan.
+    #[test]
+    fn random_selection_is_uniform_over_few_healthy_ips_in_a_large_pool() {
+        let client = pool(100);
+        for index in 3..100 {
+            client.mark_unhealthy(index);
+        }
+
+        let counts = counts(&client, 6_000);
+        assert_uniform(&counts[..3], 2_000);
+        assert!(counts[3..].iter().all(|&count| count == 0));
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use crate::bundle::BundleId;
+
+/// Default number of labeled bundles remembered before the oldest are evicted.
+pub const DEFAULT_LABEL_INDEX_CAPACITY: usize = 10_000;
+
+/// In-memory index from user-assigned labels to submitted bundle IDs, shared
+/// across clones. Holds at most `capacity` bundles; once full, the oldest
+/// submission is evicted first, whatever its label.
+#[derive(Debug, Clone)]
+pub struct LabelIndex {
+    capacity: usize,
+    inner: Arc<Mutex<LabelIndexInner>>,
+}
+
+#[derive(Debug, Default)]
+struct LabelIndexInner {
+    by_label: HashMap<String, Vec<BundleId>>,
+    order: VecDeque<(String, BundleId)>,
+}
+
+impl LabelIndex {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Arc::new(Mutex::new(LabelIndexInner::default())),
+        }
+    }
+
+    pub fn insert(&self, label: &str, bundle_id: BundleId) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+        while inner.order.len() >= self.capacity {
+            let Some((old_label, old_id)) = inner.order.pop_front() else {
+                break;
+            };
+            if let Some(ids) = inner.by_label.get_mut(&old_label) {
+                ids.retain(|id| *id != old_id);
+                if ids.is_empty() {
+                    inner.by_label.remove(&old_label);
+                }
+            }
+        }
+
+        inner
+            .by_label
+            .entry(label.to_string())
+            .or_default()
+            .push(bundle_id.clone());
+        inner.order.push_back((label.to_string(), bundle_id));
+    }
+
+    /// Bundle IDs submitted under `label`, oldest first.
+    pub fn get(&self, label: &str) -> Vec<BundleId> {
+        self.inner
+            .lock()
+            .unwrap()
+            .by_label
+            .get(label)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn labels(&self) -> Vec<String> {
+        self.inner
+            .lock()
+            .unwrap()
+            .by_label
+            .keys()
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for LabelIndex {
+    fn default() -> Self {
+        Self::new(DEFAULT_LABEL_INDEX_CAPACITY)
+    }
+}
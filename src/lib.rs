@@ -1,19 +1,157 @@
 use anyhow::{anyhow, Result};
-use rand::seq::SliceRandom;
-use reqwest::Client;
+use base64::{engine::general_purpose, Engine as _};
+use futures::{stream, Stream};
+use rand::{seq::SliceRandom, Rng};
+use reqwest::{
+    header::{
+        HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, PROXY_AUTHORIZATION, RETRY_AFTER,
+    },
+    Client, RequestBuilder, StatusCode,
+};
+use serde::Deserialize;
 use serde_json::{json, Value};
-use std::{fmt, net::IpAddr};
-use tracing::{debug, trace};
+#[cfg(feature = "solana-rpc")]
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::RpcRequest};
+use solana_instruction::Instruction;
+#[cfg(feature = "solana-rpc")]
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+#[cfg(feature = "solana-rpc")]
+use solana_signer::Signer;
+use solana_transaction::{versioned::VersionedTransaction, Transaction};
+use std::{
+    fmt,
+    net::IpAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use tokio::time::{sleep, sleep_until, timeout};
+use tracing::{debug, info, trace, warn};
 
+pub mod builder;
+pub mod bundle;
+#[cfg(feature = "cert-pinning")]
+pub mod cert_pin;
+pub mod error;
 pub mod http_client;
-use http_client::{HttpClient, IpSelectAlgorithm};
+pub mod labels;
+pub mod queue;
+pub mod region;
+pub mod response;
+#[cfg(feature = "solana-rpc")]
+pub mod simulation;
+pub mod status;
+pub mod submissions;
+pub mod tip;
+pub mod tip_stats;
+pub use builder::{ConfigError, ConfigSummary, JitoSdkBuilder};
+use bundle::{decode_bundle_params, BundleByTransaction, MAX_BUNDLE_TRANSACTIONS};
+pub use bundle::{
+    encode_versioned_transactions, sign_bundle_transactions, transfer_transactions,
+    validate_encoded_transactions, BundleBuilder, BundleConfirmation, BundleId, BundleOptions,
+    BundleOutcome, DuplicatePolicy, Encoding, Explorer, OrderingWarning, PreparedBundle,
+    TransferSpec, BUNDLE_BY_TRANSACTION_URL,
+};
+pub use error::JitoError;
+use http_client::{
+    default_client, AuthKeys, ConnectionLimiter, HttpClient, InFlightCounter, IpSelectAlgorithm,
+    RequestInterceptor, RequestTimeouts, RttTracker,
+};
+pub use http_client::{FailoverOrder, RetryPolicy, DEFAULT_REQUEST_TIMEOUT};
+use labels::LabelIndex;
+use queue::BundleQueue;
+use region::RegionSwitcher;
+pub use region::{JitoRegion, Network, RegionLatency, RegionSwitching};
+pub use response::{RequestDebug, Resubmission, RpcErrorData, SendBundleResponse, SendTxnResponse};
+#[cfg(feature = "solana-rpc")]
+use simulation::{simulate_params, BundleSimulation};
+#[cfg(feature = "solana-rpc")]
+pub use simulation::{SimulateOptions, SimulatedTransaction};
+use status::{status_entries, BundleStatusEntry, InflightStatusEntry, MAX_STATUS_IDS_PER_REQUEST};
+pub use status::{
+    AdaptiveDelay, BundleEvent, BundleStatus, CombinedStatus, CommitmentLevel, ConfirmOptions,
+    InflightBundleStatus,
+};
+use submissions::RecentSubmissions;
+use tip::{
+    append_instruction, pays_tip, tip_lamports, versioned_tip_lamports, TIP_ACCOUNT_COUNT,
+    TIP_FLOOR_URL,
+};
+pub use tip::{
+    compute_unit_limit_ix, compute_unit_price_ix, dedupe_compute_budget, extract_compute_budget,
+    memo_ix, tip_ix, CompetitiveConfig, ComputeBudgetInfo, DefaultTip, TipAccountStrategy,
+    TipAccounts, TipCheck, TipEscalation, TipFloor, TipPercentile, COMPUTE_BUDGET_PROGRAM_ID,
+    MAINNET_TIP_ACCOUNTS, MEMO_PROGRAM_ID, TESTNET_TIP_ACCOUNTS,
+};
+#[cfg(feature = "solana-rpc")]
+use tip::{find_tip_transfer, set_transfer_lamports};
+use tip_stats::TipLandStats;
+pub use tip_stats::{TipAccountStats, TipSpend, TipSpendTotals};
+
+// Header some engine deployments use to report a bundle ID: the bundle a
+// sendTransaction call was wrapped in, or the ID of a submitted bundle.
+// Its presence is best-effort.
+const BUNDLE_ID_HEADER: &str = "x-bundle-id";
+
+/// Value of the `jsonrpc` field sent unless overridden with
+/// `JitoSdkBuilder::jsonrpc_version`.
+pub const DEFAULT_JSONRPC_VERSION: &str = "2.0";
+
+struct RawResponse {
+    // Base URL of the endpoint that served the request.
+    base_url: String,
+    // Requests sent across all endpoints, 1 when nothing was retried.
+    attempts: u32,
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Value,
+    rtt: Duration,
+}
 
 pub struct JitoJsonRpcSDK {
     base_url: String,
+    fallback_urls: Vec<String>,
+    failover_order: FailoverOrder,
+    // Index into the base URL followed by `fallback_urls` of the endpoint
+    // that last served a request.
+    last_good_endpoint: AtomicUsize,
     uuid: Option<String>,
     client: Client,
     // ip pool
     client_pool: Option<HttpClient>,
+    connection_limiter: Option<ConnectionLimiter>,
+    request_interceptor: Option<RequestInterceptor>,
+    timeouts: RequestTimeouts,
+    connect_timeout: Option<Duration>,
+    auth_keys: Option<AuthKeys>,
+    tip_account_allowlist: Option<Vec<Pubkey>>,
+    default_tip: Option<DefaultTip>,
+    tip_check: TipCheck,
+    max_tip_lamports: Option<u64>,
+    tip_account_strategy: TipAccountStrategy,
+    network: Network,
+    tip_stats: TipLandStats,
+    tip_spend: TipSpend,
+    // Last tip account set fetched by `get_tip_accounts_typed`.
+    last_tip_accounts: Mutex<Option<Vec<Pubkey>>>,
+    connection_retries: u32,
+    retry_policy: RetryPolicy,
+    jsonrpc_version: String,
+    #[cfg(feature = "cert-pinning")]
+    cert_pins: Vec<[u8; 32]>,
+    labels: LabelIndex,
+    recent_submissions: Option<RecentSubmissions>,
+    bundle_queue: BundleQueue,
+    rtt: RttTracker,
+    in_flight: InFlightCounter,
+    #[cfg(feature = "solana-rpc")]
+    solana_rpc: Option<Arc<RpcClient>>,
+    #[cfg(feature = "solana-rpc")]
+    payer_balance_reserve: Option<u64>,
 }
 
 #[derive(Debug)]
@@ -38,20 +176,10 @@ impl JitoJsonRpcSDK {
         ips: Vec<String>,
         algorithm: IpSelectAlgorithm,
     ) -> Result<Self> {
-        let ips: Vec<IpAddr> = ips
-            .into_iter()
-            .map(|s| {
-                s.parse::<IpAddr>()
-                    .map_err(|e| anyhow!("Parse ip failed: {} {}", s, e))
-            })
-            .collect::<Result<Vec<_>>>()?;
-
-        let client_pool = HttpClient::new(ips, algorithm)?;
+        let client_pool = HttpClient::new(parse_ips(ips)?, algorithm, DEFAULT_REQUEST_TIMEOUT)?;
         Ok(Self {
-            base_url: base_url.to_string(),
-            uuid,
-            client: Client::new(),
             client_pool: Some(client_pool),
+            ..Self::new(base_url, uuid)
         })
     }
 
@@ -64,9 +192,156 @@ impl JitoJsonRpcSDK {
     pub fn new(base_url: &str, uuid: Option<String>) -> Self {
         Self {
             base_url: base_url.to_string(),
+            fallback_urls: Vec::new(),
+            failover_order: FailoverOrder::default(),
+            last_good_endpoint: AtomicUsize::new(0),
             uuid,
-            client: Client::new(),
+            client: default_client(),
             client_pool: None,
+            connection_limiter: None,
+            request_interceptor: None,
+            timeouts: RequestTimeouts::default(),
+            connect_timeout: None,
+            auth_keys: None,
+            tip_account_allowlist: None,
+            default_tip: None,
+            tip_check: TipCheck::default(),
+            max_tip_lamports: None,
+            tip_account_strategy: TipAccountStrategy::default(),
+            network: Network::default(),
+            tip_stats: TipLandStats::default(),
+            tip_spend: TipSpend::default(),
+            last_tip_accounts: Mutex::new(None),
+            connection_retries: 0,
+            retry_policy: RetryPolicy::default(),
+            jsonrpc_version: DEFAULT_JSONRPC_VERSION.to_string(),
+            #[cfg(feature = "cert-pinning")]
+            cert_pins: Vec::new(),
+            labels: LabelIndex::default(),
+            recent_submissions: None,
+            bundle_queue: BundleQueue::new(1),
+            rtt: RttTracker::default(),
+            in_flight: InFlightCounter::default(),
+            #[cfg(feature = "solana-rpc")]
+            solana_rpc: None,
+            #[cfg(feature = "solana-rpc")]
+            payer_balance_reserve: None,
+        }
+    }
+
+    /// `new` with the base URL of `region`.
+    pub fn for_region(region: JitoRegion, uuid: Option<String>) -> Self {
+        Self::new(region.base_url(), uuid)
+    }
+
+    /// Client failing over across `urls`, tried in order; see
+    /// `JitoSdkBuilder::endpoints`. Fails if `urls` is empty.
+    pub fn with_endpoints(urls: Vec<String>, uuid: Option<String>) -> Result<Self> {
+        let primary = urls
+            .first()
+            .cloned()
+            .ok_or_else(|| anyhow!("with_endpoints needs at least one URL"))?;
+        let builder = Self::builder(&primary).endpoints(urls);
+        match uuid {
+            Some(uuid) => builder.uuid(uuid).build(),
+            None => builder.build(),
+        }
+    }
+
+    pub fn builder(base_url: &str) -> JitoSdkBuilder {
+        JitoSdkBuilder::new(base_url)
+    }
+
+    /// Smoothed round-trip time of recent requests, measured up to the
+    /// response headers. `None` until the first response arrives.
+    pub fn rtt_estimate(&self) -> Option<Duration> {
+        self.rtt.estimate()
+    }
+
+    /// JSON-RPC requests to the block engine currently awaiting a response,
+    /// including any waiting for a slot under `max_connections_per_region`.
+    /// A steadily climbing count means the engine is slow to answer. Tip
+    /// floor, explorer and Solana RPC calls are not counted.
+    pub fn in_flight_requests(&self) -> usize {
+        self.in_flight.count()
+    }
+
+    /// Whether new connections race the host's IPv4 and IPv6 addresses,
+    /// starting the fallback family after `HAPPY_EYEBALLS_DELAY`. True
+    /// unless an IP pool is configured: each pooled client binds a local
+    /// address, which restricts it to that address's family.
+    pub fn happy_eyeballs_enabled(&self) -> bool {
+        self.client_pool.is_none()
+    }
+
+    /// The effective configuration, with secrets redacted, for logging at
+    /// startup. See `ConfigSummary` for what is left out.
+    pub fn config_summary(&self) -> ConfigSummary {
+        let millis = |timeout: Duration| timeout.as_millis() as u64;
+
+        ConfigSummary {
+            base_url: ConfigSummary::redact_url(&self.base_url),
+            fallback_urls: self
+                .fallback_urls
+                .iter()
+                .map(|url| ConfigSummary::redact_url(url))
+                .collect(),
+            region: reqwest::Url::parse(&self.base_url)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string)),
+            uuid_configured: self.uuid.is_some(),
+            auth_keys_configured: self.auth_keys.is_some(),
+            default_timeout_ms: self.timeouts.default_timeout().map(millis),
+            method_timeouts_ms: self
+                .timeouts
+                .per_method()
+                .iter()
+                .map(|(method, &timeout)| (method.clone(), millis(timeout)))
+                .collect(),
+            connection_retries: self.connection_retries,
+            rate_limit_retries: self.retry_policy.max_retries,
+            ip_count: self.client_pool.as_ref().map(HttpClient::clients_len),
+            max_connections_per_region: self
+                .connection_limiter
+                .as_ref()
+                .map(ConnectionLimiter::max_per_endpoint),
+            connect_timeout_ms: self.connect_timeout.map(millis),
+            happy_eyeballs: self.happy_eyeballs_enabled(),
+            jsonrpc_version: self.jsonrpc_version.clone(),
+            #[cfg(feature = "solana-rpc")]
+            solana_rpc_configured: self.solana_rpc.is_some(),
+            #[cfg(not(feature = "solana-rpc"))]
+            solana_rpc_configured: false,
+            #[cfg(feature = "cert-pinning")]
+            cert_pinned: !self.cert_pins.is_empty(),
+            #[cfg(not(feature = "cert-pinning"))]
+            cert_pinned: false,
+            max_tip_lamports: self.max_tip_lamports,
+        }
+    }
+
+    // Next client from the IP pool, or the default client without one.
+    fn http_client(&self) -> Client {
+        if self.client_pool.is_some() {
+            self.client()
+        } else {
+            self.client.clone()
+        }
+    }
+
+    // Request body for the block engine, using the configured `jsonrpc`
+    // version.
+    fn request_body(&self, method: &str, params: Value) -> Result<Vec<u8>, JitoError> {
+        versioned_request_body(&self.jsonrpc_version, method, params)
+    }
+
+    // Both the SDK-wide and the per-call UUID are sent as the `uuid` query
+    // parameter; the per-call value replaces the SDK-wide one rather than
+    // being sent alongside it.
+    fn endpoint_with_uuid(&self, path: &str, uuid: Option<&str>) -> String {
+        match uuid.or(self.uuid.as_deref()) {
+            Some(uuid) => format!("{}?uuid={}", path, uuid),
+            None => path.to_string(),
         }
     }
 
@@ -75,60 +350,539 @@ impl JitoJsonRpcSDK {
         endpoint: &str,
         method: &str,
         params: Option<Value>,
-    ) -> Result<Value, reqwest::Error> {
-        let url = format!("{}{}", self.base_url, endpoint);
-
-        let data = json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": method,
-            "params": params.unwrap_or(json!([]))
-        });
+    ) -> Result<Value, JitoError> {
+        self.send_request_with_headers(endpoint, method, params)
+            .await
+            .map(|(_, body)| body)
+    }
 
-        trace!("Sending request to: {}", url);
-        trace!(
-            "Request body: {}",
-            serde_json::to_string_pretty(&data).unwrap()
-        );
+    async fn send_request_with_headers(
+        &self,
+        endpoint: &str,
+        method: &str,
+        params: Option<Value>,
+    ) -> Result<(HeaderMap, Value), JitoError> {
+        // Serialized once up front; the bytes are handed to reqwest as-is.
+        let body = self.request_body(method, params.unwrap_or(json!([])))?;
 
-        let response = if self.client_pool.is_some() {
-            self.client()
-                .post(&url)
-                .header("Content-Type", "application/json")
-                .json(&data)
-                .send()
-                .await?
-        } else {
-            self.client
-                .post(&url)
-                .header("Content-Type", "application/json")
-                .json(&data)
-                .send()
-                .await?
+        self.send_raw(endpoint, method, body)
+            .await
+            .map(|response| (response.headers, response.body))
+    }
+
+    fn build_request(&self, url: &str, method: &str, body: Vec<u8>) -> RequestBuilder {
+        let mut request = self
+            .http_client()
+            .post(url)
+            .header("Content-Type", "application/json")
+            .body(body);
+        if let Some(auth_keys) = &self.auth_keys {
+            request = request.header(http_client::AUTH_HEADER, auth_keys.next_key());
+        }
+        if let Some(timeout) = self.timeouts.for_method(method) {
+            request = request.timeout(timeout);
+        }
+        if let Some(interceptor) = &self.request_interceptor {
+            request = interceptor.apply(request);
+        }
+        request
+    }
+
+    // Reports a timeout with the limit that hit, `connect_timeout` while
+    // connecting and the per-method limit otherwise, and how long the
+    // request actually ran.
+    fn request_error(&self, err: reqwest::Error, method: &str, sent_at: Instant) -> JitoError {
+        let limit = match self.connect_timeout {
+            Some(connect_timeout) if err.is_connect() => Some(connect_timeout),
+            _ => self.timeouts.for_method(method),
+        };
+        match limit {
+            Some(limit) if err.is_timeout() => JitoError::Timeout {
+                elapsed: sent_at.elapsed(),
+                limit,
+            },
+            _ => err.into(),
+        }
+    }
+
+    // Tries the primary endpoint and then each fallback in order, moving on
+    // after a connection failure or a 5xx response from all but the last.
+    async fn send_raw(
+        &self,
+        endpoint: &str,
+        method: &str,
+        mut body: Vec<u8>,
+    ) -> Result<RawResponse, JitoError> {
+        let mut endpoints = self.endpoint_order().into_iter().peekable();
+        let mut attempts = 0;
+        let _in_flight = self.in_flight.start();
+
+        // The connection permit is kept until the body has been read, since
+        // the connection stays open while it streams.
+        let (base_url, response, sent_at, _permit) = loop {
+            let index = endpoints
+                .next()
+                .expect("the primary endpoint is always present");
+            let base_url = self.endpoint_url(index);
+            let is_last = endpoints.peek().is_none();
+            let url = format!("{}{}", base_url, endpoint);
+
+            trace!("Sending request to: {}", url);
+            trace!("Request body: {}", String::from_utf8_lossy(&body));
+
+            let permit = match &self.connection_limiter {
+                Some(limiter) => Some(limiter.acquire(base_url).await),
+                None => None,
+            };
+            match self
+                .send_attempts(&url, method, &mut body, is_last, &mut attempts)
+                .await
+            {
+                (Err(e), _) if !is_last && e.is_connect() => {
+                    warn!("{} unreachable, failing over: {}", base_url, e);
+                }
+                (Ok(response), _) if !is_last && response.status().is_server_error() => {
+                    warn!("{} returned {}, failing over", base_url, response.status());
+                }
+                (result, sent_at) => {
+                    let response = result.map_err(|e| self.request_error(e, method, sent_at))?;
+                    if !response.status().is_server_error() {
+                        self.last_good_endpoint.store(index, Ordering::Relaxed);
+                    }
+                    break (base_url.clone(), response, sent_at, permit);
+                }
+            }
         };
+        let rtt = sent_at.elapsed();
+        self.rtt.record(rtt);
 
         let status = response.status();
-        debug!("Response status: {}", status);
+        debug!("Response status: {} from {}", status, base_url);
+        let headers = response.headers().clone();
 
-        let body = response.json::<Value>().await?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| self.request_error(e, method, sent_at))?;
+        let body = serde_json::from_slice::<Value>(&bytes)
+            .map_err(|_| unexpected_response(&headers, &bytes))?;
         trace!(
             "Response body: {}",
             serde_json::to_string_pretty(&body).unwrap()
         );
 
-        Ok(body)
+        Ok(RawResponse {
+            base_url,
+            attempts,
+            status,
+            headers,
+            body,
+            rtt,
+        })
     }
 
-    pub async fn get_tip_accounts(&self) -> Result<Value, reqwest::Error> {
-        let endpoint = if let Some(uuid) = &self.uuid {
-            format!("/bundles?uuid={}", uuid)
-        } else {
-            "/bundles".to_string()
+    // Sends to one endpoint, retrying connection failures immediately and
+    // 429 responses as `retry_policy` says, and counts every send in
+    // `attempts`. `body` is only copied while another attempt may follow,
+    // here or at a later endpoint.
+    async fn send_attempts(
+        &self,
+        url: &str,
+        method: &str,
+        body: &mut Vec<u8>,
+        is_last_endpoint: bool,
+        attempts: &mut u32,
+    ) -> (Result<reqwest::Response, reqwest::Error>, Instant) {
+        let mut retries_left = self.connection_retries;
+        let mut rate_limit_retries = 0;
+        loop {
+            let more_attempts = retries_left > 0
+                || rate_limit_retries < self.retry_policy.max_retries
+                || !is_last_endpoint;
+            let attempt_body = if more_attempts {
+                body.clone()
+            } else {
+                std::mem::take(body)
+            };
+            *attempts += 1;
+            let sent_at = Instant::now();
+            match self.build_request(url, method, attempt_body).send().await {
+                Err(e) if retries_left > 0 && is_retryable(&e, method) => {
+                    retries_left -= 1;
+                    warn!(
+                        attempt = *attempts + 1,
+                        backoff_ms = 0,
+                        "Retrying {} after connection failure: {}",
+                        method,
+                        e
+                    );
+                }
+                Ok(response)
+                    if response.status() == StatusCode::TOO_MANY_REQUESTS
+                        && rate_limit_retries < self.retry_policy.max_retries =>
+                {
+                    let delay = self
+                        .retry_policy
+                        .delay(rate_limit_retries, retry_after(response.headers()));
+                    rate_limit_retries += 1;
+                    warn!(
+                        attempt = *attempts + 1,
+                        backoff_ms = delay.as_millis() as u64,
+                        "Retrying {} after rate limiting",
+                        method
+                    );
+                    sleep(delay).await;
+                }
+                result => return (result, sent_at),
+            }
+        }
+    }
+
+    // Indices of the endpoints a request tries, in order: the one that last
+    // served a request, then the others as `failover_order` says.
+    fn endpoint_order(&self) -> Vec<usize> {
+        let count = 1 + self.fallback_urls.len();
+        let start = self.last_good_endpoint.load(Ordering::Relaxed) % count;
+        let mut order: Vec<usize> = (0..count).map(|i| (start + i) % count).collect();
+        if self.failover_order == FailoverOrder::Randomized {
+            order[1..].shuffle(&mut rand::thread_rng());
+        }
+        order
+    }
+
+    fn endpoint_url(&self, index: usize) -> &String {
+        match index {
+            0 => &self.base_url,
+            i => &self.fallback_urls[i - 1],
+        }
+    }
+
+    /// Base URL of the endpoint that last served a request, which is where
+    /// the next request starts. This is the primary URL until a fallback
+    /// answers in its place.
+    pub fn current_base_url(&self) -> &str {
+        self.endpoint_url(self.last_good_endpoint.load(Ordering::Relaxed))
+    }
+
+    /// Region of the endpoint requests currently start at, `None` if its base
+    /// URL isn't one of `JitoRegion`'s.
+    pub fn active_region(&self) -> Option<JitoRegion> {
+        JitoRegion::from_base_url(self.current_base_url())
+    }
+
+    /// Round trip of a `getTipAccounts` request to each configured endpoint,
+    /// primary first, measured concurrently up to the response headers.
+    /// Failover and retries are bypassed, so each failure is reported as is.
+    pub async fn measure_region_latencies(&self) -> Vec<RegionLatency> {
+        let endpoint = self.endpoint_with_uuid("/bundles", None);
+        let body = self
+            .request_body("getTipAccounts", json!([]))
+            .expect("JSON values always serialize");
+
+        let probes = (0..=self.fallback_urls.len()).map(|index| {
+            let base_url = self.endpoint_url(index);
+            let url = format!("{}{}", base_url, endpoint);
+            let request = self.build_request(&url, "getTipAccounts", body.clone());
+            async move {
+                let started = Instant::now();
+                let latency = match request.send().await {
+                    Ok(response) => response
+                        .error_for_status()
+                        .map(|_| started.elapsed())
+                        .map_err(JitoError::from),
+                    Err(e) => Err(e.into()),
+                };
+                RegionLatency {
+                    base_url: base_url.clone(),
+                    region: JitoRegion::from_base_url(base_url),
+                    latency,
+                }
+            }
+        });
+        futures::future::join_all(probes).await
+    }
+
+    /// Spawns a task that runs `measure_region_latencies` every
+    /// `config.interval` and moves requests to the fastest endpoint once it
+    /// has beaten the active one by `config.margin` in
+    /// `config.consecutive_rounds` measurements in a row. Switches are
+    /// logged at `info` level. Only configured endpoints are considered,
+    /// so set fallbacks, e.g. with `with_endpoints`. The task ends once the
+    /// SDK is dropped; abort the returned handle to stop it sooner.
+    pub fn spawn_region_switching(
+        self: &Arc<Self>,
+        config: RegionSwitching,
+    ) -> tokio::task::JoinHandle<()> {
+        let weak = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut switcher = RegionSwitcher::default();
+            loop {
+                sleep(config.interval).await;
+                let Some(sdk) = weak.upgrade() else {
+                    return;
+                };
+                let latencies: Vec<Option<Duration>> = sdk
+                    .measure_region_latencies()
+                    .await
+                    .into_iter()
+                    .map(|probe| probe.latency.ok())
+                    .collect();
+                let active = sdk.last_good_endpoint.load(Ordering::Relaxed) % latencies.len();
+                if let Some(next) = switcher.observe(active, &latencies, &config) {
+                    info!(
+                        "Switching active region from {} to {}",
+                        sdk.endpoint_url(active),
+                        sdk.endpoint_url(next)
+                    );
+                    sdk.last_good_endpoint.store(next, Ordering::Relaxed);
+                }
+            }
+        })
+    }
+
+    /// The `curl` command that would reproduce a call to `method`, with the
+    /// UUID redacted. Nothing is sent. Headers added by a request
+    /// interceptor are not included.
+    pub fn call_as_curl(&self, method: &str, params: Option<Value>) -> String {
+        let endpoint = self.endpoint_with_uuid(method_path(method), None);
+        let url = redact_uuid(&format!("{}{}", self.current_base_url(), endpoint));
+        let body = self
+            .request_body(method, params.unwrap_or(json!([])))
+            .expect("JSON values always serialize");
+
+        let auth = match self.auth_keys {
+            Some(_) => format!(" -H '{}: {}'", http_client::AUTH_HEADER, REDACTED),
+            None => String::new(),
+        };
+        format!(
+            "curl -X POST {} -H 'Content-Type: application/json'{} -d {}",
+            shell_quote(&url),
+            auth,
+            shell_quote(&String::from_utf8_lossy(&body))
+        )
+    }
+
+    /// Calls `method` like the typed methods do and also returns what went
+    /// over the wire, for one-off debugging. The request is sent to the
+    /// endpoint the SDK uses for that method; with fallback endpoints
+    /// configured, `RequestDebug::url` is the one that served it.
+    pub async fn call_verbose(
+        &self,
+        method: &str,
+        params: Option<Value>,
+    ) -> Result<(Value, RequestDebug), JitoError> {
+        let endpoint = self.endpoint_with_uuid(method_path(method), None);
+        let body = self.request_body(method, params.unwrap_or(json!([])))?;
+        let request_body = String::from_utf8_lossy(&body).into_owned();
+
+        let response = self.send_raw(&endpoint, method, body).await?;
+        let debug = RequestDebug {
+            url: redact_uuid(&format!("{}{}", response.base_url, endpoint)),
+            request_body,
+            status: response.status,
+            response_headers: redact_auth_headers(response.headers),
+            rtt: response.rtt,
+            attempts: response.attempts,
         };
 
+        Ok((response.body, debug))
+    }
+
+    pub async fn get_tip_accounts(&self) -> Result<Value, JitoError> {
+        let endpoint = self.endpoint_with_uuid("/bundles", None);
+
         self.send_request(&endpoint, "getTipAccounts", None).await
     }
 
+    /// Typed variant of `get_tip_accounts`, recording when the set was fetched.
+    pub async fn get_tip_accounts_typed(&self) -> Result<TipAccounts, JitoError> {
+        let response = self.get_tip_accounts().await?;
+        let fetched_at = Instant::now();
+
+        let accounts = rpc_result(response)?
+            .as_array()
+            .ok_or_else(|| {
+                JitoError::MalformedResponse("tip accounts are not an array".to_string())
+            })?
+            .iter()
+            .map(|account| {
+                account
+                    .as_str()
+                    .and_then(|s| s.parse::<Pubkey>().ok())
+                    .ok_or_else(|| JitoError::InvalidTipAccount {
+                        value: account.to_string(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let accounts = self.allowed_tip_accounts(accounts)?;
+        *self.last_tip_accounts.lock().unwrap() = Some(accounts.clone());
+
+        Ok(TipAccounts {
+            accounts,
+            fetched_at,
+        })
+    }
+
+    /// Fetches the latest landed-tip percentiles from the tip floor endpoint.
+    pub async fn get_tip_floor(&self) -> Result<TipFloor, JitoError> {
+        trace!("Fetching tip floor from: {}", TIP_FLOOR_URL);
+        let response = self.http_client().get(TIP_FLOOR_URL).send().await?;
+        debug!("Tip floor response status: {}", response.status());
+
+        response
+            .json::<Vec<TipFloor>>()
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| JitoError::MalformedResponse("empty tip floor response".to_string()))
+    }
+
+    /// The bundle that landed `signature`, looked up on the public bundle
+    /// explorer at `BUNDLE_BY_TRANSACTION_URL`. Only landed mainnet bundles
+    /// are indexed, and only after a short delay, so `None` means the
+    /// mapping isn't known yet rather than that the transaction wasn't
+    /// bundled. The reverse lookup is the `transactions` of `BundleOutcome`.
+    pub async fn bundle_id_for_signature(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<BundleId>, JitoError> {
+        let url = format!("{}/{}", BUNDLE_BY_TRANSACTION_URL, signature);
+        trace!("Fetching bundle for transaction from: {}", url);
+        let response = self.http_client().get(&url).send().await?;
+        debug!("Bundle lookup response status: {}", response.status());
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        Ok(response
+            .error_for_status()?
+            .json::<Vec<BundleByTransaction>>()
+            .await?
+            .into_iter()
+            .next()
+            .map(|entry| entry.bundle_id))
+    }
+
+    /// Whether `tip_lamports` meets or exceeds the current tip floor at
+    /// `target_percentile`.
+    pub async fn is_tip_competitive(
+        &self,
+        tip_lamports: u64,
+        target_percentile: TipPercentile,
+    ) -> Result<bool, JitoError> {
+        let floor = self.get_tip_floor().await?;
+        Ok(tip_lamports >= floor.lamports(target_percentile))
+    }
+
+    /// The tip a helper should pay: `tip_lamports` when given, otherwise the
+    /// default configured with `JitoSdkBuilder::default_tip`, fetching the
+    /// tip floor for a percentile default. Fails with `JitoError::NoTipAmount`
+    /// when there is neither. `send_transfers` applies it itself; use it to
+    /// get the default for helpers that take an amount, such as
+    /// `build_tip_instruction`.
+    pub async fn resolve_tip(&self, tip_lamports: Option<u64>) -> Result<u64, JitoError> {
+        match (tip_lamports, self.default_tip) {
+            (Some(lamports), _) => Ok(lamports),
+            (None, Some(DefaultTip::Lamports(lamports))) => Ok(lamports),
+            (None, Some(DefaultTip::Percentile(percentile))) => {
+                Ok(self.get_tip_floor().await?.lamports(percentile))
+            }
+            (None, None) => Err(JitoError::NoTipAmount),
+        }
+    }
+
+    /// Tip in lamports at `percentile` of the current tip floor, or
+    /// `fallback_lamports` when the floor can't be fetched. The tip floor is
+    /// served from a different host than the block engine and can be down on
+    /// its own, so this keeps tipping sensible through a partial outage.
+    pub async fn recommended_tip_or_default(
+        &self,
+        percentile: TipPercentile,
+        fallback_lamports: u64,
+    ) -> u64 {
+        match self.get_tip_floor().await {
+            Ok(floor) => floor.lamports(percentile),
+            Err(e) => {
+                warn!(
+                    "Tip floor unavailable, falling back to {} lamports: {}",
+                    fallback_lamports, e
+                );
+                fallback_lamports
+            }
+        }
+    }
+
+    /// Tip instructions paying `total_lamports` from `from`, split evenly
+    /// across `n_accounts` distinct, randomly chosen tip accounts to hedge
+    /// against the leader favoring particular accounts. Any remainder goes
+    /// one lamport each to the first accounts.
+    pub async fn tip_instructions_split(
+        &self,
+        from: &Pubkey,
+        total_lamports: u64,
+        n_accounts: usize,
+    ) -> Result<Vec<Instruction>, JitoError> {
+        if n_accounts == 0 || n_accounts > TIP_ACCOUNT_COUNT {
+            return Err(JitoError::InvalidTipSplit(format!(
+                "n_accounts must be between 1 and {}, got {}",
+                TIP_ACCOUNT_COUNT, n_accounts
+            )));
+        }
+
+        let tip_accounts = self.get_tip_accounts_typed().await?;
+        if tip_accounts.accounts.len() < n_accounts {
+            return Err(JitoError::InvalidTipSplit(format!(
+                "only {} tip accounts available, {} requested",
+                tip_accounts.accounts.len(),
+                n_accounts
+            )));
+        }
+
+        let share = total_lamports / n_accounts as u64;
+        let remainder = (total_lamports % n_accounts as u64) as usize;
+        Ok(tip_accounts
+            .accounts
+            .choose_multiple(&mut rand::thread_rng(), n_accounts)
+            .enumerate()
+            .map(|(i, account)| tip_ix(from, account, share + u64::from(i < remainder)))
+            .collect())
+    }
+
+    /// Transfer of `lamports` from `from` to a tip account picked as
+    /// `get_random_tip_account_within` does, without a time limit, so the
+    /// account is always a current, allowlisted one.
+    pub async fn build_tip_instruction(
+        &self,
+        from: &Pubkey,
+        lamports: u64,
+    ) -> Result<Instruction, JitoError> {
+        let accounts = self.get_tip_accounts_typed().await?.accounts;
+        let account = self
+            .pick_tip_account(&accounts)
+            .map(|i| accounts[i])
+            .ok_or_else(|| JitoError::MalformedResponse("no tip accounts returned".to_string()))?;
+        Ok(tip_ix(from, &account, lamports))
+    }
+
+    /// Appends a `build_tip_instruction` tip to `transaction`. Its fee payer
+    /// and blockhash are kept, but signatures are reset, so sign it
+    /// afterwards.
+    pub async fn add_tip_to_transaction(
+        &self,
+        transaction: &mut Transaction,
+        from: &Pubkey,
+        lamports: u64,
+    ) -> Result<(), JitoError> {
+        let instruction = self.build_tip_instruction(from, lamports).await?;
+        append_instruction(transaction, instruction, from).ok_or_else(|| {
+            JitoError::InvalidTransactionPayload {
+                index: 0,
+                reason: "an instruction references a missing account key".to_string(),
+            }
+        })
+    }
+
     // Get a random tip account
     pub async fn get_random_tip_account(&self) -> Result<String> {
         let tip_accounts_response = self.get_tip_accounts().await?;
@@ -141,93 +895,623 @@ impl JitoJsonRpcSDK {
             return Err(anyhow!("No tip accounts available"));
         }
 
-        let random_account = tip_accounts
-            .choose(&mut rand::thread_rng())
+        let tip_accounts: Vec<&Value> = match &self.tip_account_allowlist {
+            Some(allowlist) => tip_accounts
+                .iter()
+                .filter(|account| {
+                    account
+                        .as_str()
+                        .and_then(|s| s.parse::<Pubkey>().ok())
+                        .is_some_and(|pubkey| allowlist.contains(&pubkey))
+                })
+                .collect(),
+            None => tip_accounts.iter().collect(),
+        };
+        if tip_accounts.is_empty() {
+            return Err(JitoError::NoAllowedTipAccounts.into());
+        }
+
+        // An unparseable account is still a candidate, so picking it
+        // reports the bad value below.
+        let pubkeys: Vec<Pubkey> = tip_accounts
+            .iter()
+            .map(|account| {
+                account
+                    .as_str()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or_default()
+            })
+            .collect();
+        let random_account = self
+            .pick_tip_account(&pubkeys)
+            .map(|i| tip_accounts[i])
             .ok_or_else(|| anyhow!("Failed to choose random tip account"))?;
 
-        random_account
-            .as_str()
-            .ok_or_else(|| anyhow!("Failed to parse tip account as string"))
-            .map(String::from)
+        match random_account.as_str() {
+            Some(account) if account.parse::<Pubkey>().is_ok() => Ok(account.to_string()),
+            _ => Err(JitoError::InvalidTipAccount {
+                value: random_account.to_string(),
+            }
+            .into()),
+        }
     }
 
-    pub async fn get_bundle_statuses(&self, bundle_uuids: Vec<String>) -> Result<Value> {
-        let endpoint = if let Some(uuid) = &self.uuid {
-            format!("/getBundleStatuses?uuid={}", uuid)
-        } else {
-            "/getBundleStatuses".to_string()
+    /// `get_random_tip_account` for the hot submission path: if fetching the
+    /// tip accounts fails or takes longer than `limit`, picks from the set
+    /// fetched last by any typed tip account call instead of waiting. Before
+    /// any set has been fetched, falls back to `TipAccounts::known_for` the
+    /// configured network.
+    pub async fn get_random_tip_account_within(
+        &self,
+        limit: Duration,
+    ) -> Result<Pubkey, JitoError> {
+        let accounts = match timeout(limit, self.get_tip_accounts_typed()).await {
+            Ok(Ok(accounts)) => accounts.accounts,
+            // The allowlist rejected the engine's set; the static one can't
+            // do better.
+            Ok(Err(JitoError::NoAllowedTipAccounts)) => {
+                return Err(JitoError::NoAllowedTipAccounts)
+            }
+            Ok(Err(e)) => {
+                warn!("Tip account fetch failed: {}", e);
+                self.fallback_tip_accounts()?
+            }
+            Err(_) => {
+                warn!("Tip account fetch exceeded {:?}", limit);
+                self.fallback_tip_accounts()?
+            }
+        };
+
+        self.pick_tip_account(&accounts)
+            .map(|i| accounts[i])
+            .ok_or_else(|| JitoError::MalformedResponse("no tip accounts returned".to_string()))
+    }
+
+    // The last fetched tip accounts, or the static set for the configured
+    // network if none have been fetched.
+    fn fallback_tip_accounts(&self) -> Result<Vec<Pubkey>, JitoError> {
+        if let Some(cached) = self.last_tip_accounts.lock().unwrap().clone() {
+            debug!("Using the last fetched tip accounts");
+            return Ok(cached);
+        }
+        debug!("Using the static {:?} tip accounts", self.network);
+        self.allowed_tip_accounts(TipAccounts::known_for(self.network).to_vec())
+    }
+
+    // Index of the account to tip under the configured
+    // `TipAccountStrategy`, or `None` if `accounts` is empty.
+    fn pick_tip_account(&self, accounts: &[Pubkey]) -> Option<usize> {
+        if accounts.is_empty() {
+            return None;
+        }
+        let mut rng = rand::thread_rng();
+        let weighted = match self.tip_account_strategy {
+            TipAccountStrategy::RegionOptimized => {
+                self.tip_stats
+                    .choose(self.current_base_url(), accounts, &mut rng)
+            }
+            TipAccountStrategy::Random => None,
+        };
+        Some(weighted.unwrap_or_else(|| rng.gen_range(0..accounts.len())))
+    }
+
+    /// Records whether a bundle tipping `account` through this SDK's block
+    /// engine landed, for `TipAccountStrategy::RegionOptimized`. Call it
+    /// once a bundle's outcome is known.
+    pub fn record_tip_outcome(&self, account: &Pubkey, landed: bool) {
+        self.tip_stats
+            .record(self.current_base_url(), *account, landed);
+    }
+
+    /// Outcomes recorded with `record_tip_outcome`, per region and account.
+    pub fn tip_account_stats(&self) -> Vec<TipAccountStats> {
+        self.tip_stats.snapshot()
+    }
+
+    /// Lamports tipped by bundles this SDK submitted from signed
+    /// transactions, e.g. through `send_and_track` or `enqueue_bundle`, and
+    /// accepted by the engine. Tips are recognized as transfers to the last
+    /// fetched tip accounts, or the static set for the configured network.
+    /// Bundles sent as pre-encoded params aren't counted.
+    pub fn total_tips_submitted_lamports(&self) -> u64 {
+        self.tip_spend.totals().submitted_lamports
+    }
+
+    /// Handle to the tip spend counters, with landed and failed totals for
+    /// bundles whose outcome is known, and a reset for per-session
+    /// accounting. Clones share the SDK's counters.
+    pub fn tip_spend(&self) -> TipSpend {
+        self.tip_spend.clone()
+    }
+
+    // Lamports the bundle of `transactions` pays to tip accounts.
+    fn bundle_tip_lamports(&self, transactions: &[Transaction]) -> u64 {
+        let cached = self.last_tip_accounts.lock().unwrap().clone();
+        let tip_accounts = cached.unwrap_or_else(|| TipAccounts::known_for(self.network).to_vec());
+        transactions
+            .iter()
+            .map(|transaction| tip_lamports(transaction, &tip_accounts))
+            .fold(0, u64::saturating_add)
+    }
+
+    // `bundle_tip_lamports`, failing if it exceeds `max_tip_lamports`.
+    fn capped_bundle_tip(&self, transactions: &[Transaction]) -> Result<u64, JitoError> {
+        self.check_tip_cap(self.bundle_tip_lamports(transactions))
+    }
+
+    // Applies `max_tip_lamports` to the transactions in pre-encoded
+    // `sendBundle` params. Only tip transfers that decode are counted.
+    fn check_params_tip_cap(&self, request_params: &Value) -> Result<(), JitoError> {
+        if self.max_tip_lamports.is_none() {
+            return Ok(());
+        }
+        let cached = self.last_tip_accounts.lock().unwrap().clone();
+        let tip_accounts = cached.unwrap_or_else(|| TipAccounts::known_for(self.network).to_vec());
+        let requested = decode_bundle_params(request_params)
+            .iter()
+            .map(|transaction| versioned_tip_lamports(transaction, &tip_accounts))
+            .fold(0, u64::saturating_add);
+        self.check_tip_cap(requested).map(|_| ())
+    }
+
+    fn check_tip_cap(&self, requested: u64) -> Result<u64, JitoError> {
+        match self.max_tip_lamports {
+            Some(cap) if requested > cap => Err(JitoError::TipTooHigh { requested, cap }),
+            _ => Ok(requested),
+        }
+    }
+
+    // Adds a confirmed bundle's tip to the landed or failed total. Timeouts
+    // and request errors leave the outcome unknown and aren't counted.
+    fn record_tip_spend_outcome(&self, lamports: u64, result: &Result<BundleOutcome, JitoError>) {
+        match result {
+            Ok(_) => self.tip_spend.record_outcome(lamports, true),
+            Err(JitoError::BundleFailed { .. } | JitoError::BundleDropped { .. }) => {
+                self.tip_spend.record_outcome(lamports, false)
+            }
+            Err(_) => {}
+        }
+    }
+
+    // Fetched accounts that are on the allowlist, if one is configured.
+    fn allowed_tip_accounts(&self, accounts: Vec<Pubkey>) -> Result<Vec<Pubkey>, JitoError> {
+        let Some(allowlist) = &self.tip_account_allowlist else {
+            return Ok(accounts);
         };
+        let allowed: Vec<Pubkey> = accounts
+            .into_iter()
+            .filter(|account| allowlist.contains(account))
+            .collect();
+        if allowed.is_empty() {
+            return Err(JitoError::NoAllowedTipAccounts);
+        }
+        Ok(allowed)
+    }
+
+    /// Endless stream cycling through the tip accounts, refetching the set
+    /// once `refresh` has elapsed since the last fetch. A failed refresh keeps
+    /// the previous set; until the first fetch succeeds it is retried every
+    /// `refresh`.
+    pub fn tip_account_stream(&self, refresh: Duration) -> impl Stream<Item = Pubkey> + '_ {
+        let start = (Vec::<Pubkey>::new(), 0usize, tokio::time::Instant::now());
+
+        stream::unfold(
+            start,
+            move |(mut accounts, mut next, mut refresh_at)| async move {
+                loop {
+                    if tokio::time::Instant::now() >= refresh_at {
+                        refresh_at = tokio::time::Instant::now() + refresh;
+                        match self.get_tip_accounts_typed().await {
+                            Ok(tip) if !tip.accounts.is_empty() => {
+                                accounts = tip.accounts;
+                                next %= accounts.len();
+                            }
+                            Ok(_) => warn!("No tip accounts returned, keeping the previous set"),
+                            Err(e) => warn!("Failed to refresh tip accounts: {}", e),
+                        }
+                    }
+
+                    if accounts.is_empty() {
+                        sleep_until(refresh_at).await;
+                        continue;
+                    }
+
+                    let account = accounts[next];
+                    next = (next + 1) % accounts.len();
+                    return Some((account, (accounts, next, refresh_at)));
+                }
+            },
+        )
+    }
+
+    pub async fn get_bundle_statuses(&self, bundle_uuids: Vec<String>) -> Result<Value> {
+        let endpoint = self.endpoint_with_uuid("/getBundleStatuses", None);
 
         // Construct the params as a list within a list
         let params = json!([bundle_uuids]);
 
         self.send_request(&endpoint, "getBundleStatuses", Some(params))
             .await
-            .map_err(|e| anyhow!("Request error: {}", e))
+            .map_err(Into::into)
     }
 
+    /// Submits a bundle. `uuid` overrides the SDK-wide UUID for this request
+    /// only; when it is `None` the UUID passed at construction is used, if any.
     pub async fn send_bundle(
         &self,
         params: Option<Value>,
         uuid: Option<&str>,
     ) -> Result<Value, anyhow::Error> {
-        let mut endpoint = "/bundles".to_string();
+        let endpoint = self.endpoint_with_uuid("/bundles", uuid);
 
-        if let Some(uuid) = uuid {
-            endpoint = format!("{}?uuid={}", endpoint, uuid);
-        }
+        let request_params = bundle_request_params(params)?;
+        self.check_params_tip_cap(&request_params)?;
 
-        // Create the parameters for the request
-        let request_params = match params {
-            // If params is already in the correct format [transactions, {encoding: "base64"}]
-            Some(ref value) if value.is_array() && value.as_array().unwrap().len() == 2 => {
-                // Use it as is
-                value.clone()
-            }
-            Some(Value::Array(transactions)) => {
-                // Validate transactions
-                if transactions.is_empty() {
-                    return Err(anyhow!("Bundle must contain at least one transaction"));
-                }
-                if transactions.len() > 5 {
-                    return Err(anyhow!("Bundle can contain at most 5 transactions"));
-                }
+        let (headers, body) = self
+            .send_request_with_headers(&endpoint, "sendBundle", Some(request_params))
+            .await?;
+        check_bundle_id_header(&headers, &body);
+        if let Some(bundle_id) = body["result"].as_str() {
+            self.record_submission(BundleId::from(bundle_id));
+        }
 
-                json!([
-                    transactions,
-                    {
-                        "encoding": "base64"
-                    }
-                ])
-            }
-            _ => {
-                return Err(anyhow!(
-                    "Invalid bundle format: expected an array of transactions"
-                ))
-            }
-        };
+        Ok(body)
+    }
 
-        self.send_request(&endpoint, "sendBundle", Some(request_params))
+    /// Typed variant of `send_bundle`, taking the same params. Returns the
+    /// bundle ID from the response, failing with `JitoError::RpcError` when
+    /// the engine returned a JSON-RPC error and
+    /// `JitoError::MalformedResponse` when the result holds no bundle ID.
+    pub async fn send_bundle_typed(
+        &self,
+        params: Option<Value>,
+        uuid: Option<&str>,
+    ) -> Result<BundleId, JitoError> {
+        self.send_bundle_detailed(params, uuid)
             .await
-            .map_err(|e| anyhow!("Request error: {}", e))
+            .map(|response| response.bundle_id)
     }
 
-    pub async fn send_txn(
+    /// `send_bundle_typed`, also reporting which endpoint accepted the
+    /// bundle: the configured one, or a fallback if the request failed over.
+    pub async fn send_bundle_detailed(
         &self,
         params: Option<Value>,
-        bundle_only: bool,
-    ) -> Result<Value, reqwest::Error> {
-        let mut query_params = Vec::new();
+        uuid: Option<&str>,
+    ) -> Result<SendBundleResponse, JitoError> {
+        let endpoint = self.endpoint_with_uuid("/bundles", uuid);
+        let request_params = bundle_request_params(params)?;
+        self.check_params_tip_cap(&request_params)?;
+        let body = self.request_body("sendBundle", request_params)?;
+        let response = self.send_raw(&endpoint, "sendBundle", body).await?;
 
-        if bundle_only {
-            query_params.push("bundleOnly=true".to_string());
+        let bundle_id = bundle_id_from_response(&response.headers, response.body)?;
+        self.record_submission(bundle_id.clone());
+        Ok(SendBundleResponse {
+            bundle_id,
+            region: JitoRegion::from_base_url(&response.base_url),
+            base_url: response.base_url,
+        })
+    }
+
+    /// Submits the same bundle to every region in `regions` at once and
+    /// returns the first that accepts it, with its bundle ID. Configured
+    /// endpoints, failover and retries are not used, but each request waits
+    /// for a slot under `max_connections_per_region`. Fails with the last
+    /// error when every region rejects the bundle, and with
+    /// `JitoError::NoRegions` when `regions` is empty.
+    pub async fn send_bundle_multi_region(
+        &self,
+        params: Option<Value>,
+        regions: &[JitoRegion],
+        uuid: Option<&str>,
+    ) -> Result<SendBundleResponse, JitoError> {
+        if regions.is_empty() {
+            return Err(JitoError::NoRegions);
         }
+        let endpoint = self.endpoint_with_uuid("/bundles", uuid);
+        let request_params = bundle_request_params(params)?;
+        self.check_params_tip_cap(&request_params)?;
+        let body = self.request_body("sendBundle", request_params)?;
 
-        let endpoint = if query_params.is_empty() {
-            "/transactions".to_string()
-        } else {
-            format!("/transactions?{}", query_params.join("&"))
-        };
+        let submissions = regions.iter().map(|&region| {
+            let url = format!("{}{}", region.base_url(), endpoint);
+            let request = self.build_request(&url, "sendBundle", body.clone());
+            let in_flight = self.in_flight.start();
+            Box::pin(async move {
+                let _in_flight = in_flight;
+                let _permit = match &self.connection_limiter {
+                    Some(limiter) => Some(limiter.acquire(region.base_url()).await),
+                    None => None,
+                };
+                let sent_at = Instant::now();
+                let response = request
+                    .send()
+                    .await
+                    .map_err(|e| self.request_error(e, "sendBundle", sent_at))?;
+
+                let headers = response.headers().clone();
+                let bytes = response
+                    .bytes()
+                    .await
+                    .map_err(|e| self.request_error(e, "sendBundle", sent_at))?;
+                let body = serde_json::from_slice::<Value>(&bytes)
+                    .map_err(|_| unexpected_response(&headers, &bytes))?;
+                let bundle_id = bundle_id_from_response(&headers, body)?;
+                Ok::<_, JitoError>(SendBundleResponse {
+                    bundle_id,
+                    region: Some(region),
+                    base_url: region.base_url().to_string(),
+                })
+            })
+        });
+
+        let (accepted, _) = futures::future::select_ok(submissions).await?;
+        debug!(
+            "Bundle {} accepted first by {:?}",
+            accepted.bundle_id, accepted.region
+        );
+        self.record_submission(accepted.bundle_id.clone());
+        Ok(accepted)
+    }
+
+    /// `send_bundle` with an explicit options object in place of the default
+    /// `{"encoding": "base64"}`. `transactions` must be encoded as
+    /// `options.encoding` says.
+    pub async fn send_bundle_with_options(
+        &self,
+        transactions: Vec<String>,
+        options: &BundleOptions,
+        uuid: Option<&str>,
+    ) -> Result<Value, anyhow::Error> {
+        if transactions.is_empty() {
+            return Err(anyhow!("Bundle must contain at least one transaction"));
+        }
+        if transactions.len() > MAX_BUNDLE_TRANSACTIONS {
+            return Err(anyhow!(
+                "Bundle can contain at most {} transactions",
+                MAX_BUNDLE_TRANSACTIONS
+            ));
+        }
+
+        self.send_bundle(Some(json!([transactions, options])), uuid)
+            .await
+    }
+
+    /// `send_bundle`, additionally recording the returned bundle ID under
+    /// `label` so outcomes can later be grouped per strategy with
+    /// `outcomes_by_label`.
+    pub async fn send_bundle_labeled(
+        &self,
+        params: Option<Value>,
+        uuid: Option<&str>,
+        label: &str,
+    ) -> Result<Value, anyhow::Error> {
+        let response = self.send_bundle(params, uuid).await?;
+        if let Some(bundle_id) = response["result"].as_str() {
+            self.labels.insert(label, BundleId::from(bundle_id));
+        }
+        Ok(response)
+    }
+
+    /// Bundle IDs submitted under `label` that are still in the index.
+    pub fn bundles_by_label(&self, label: &str) -> Vec<BundleId> {
+        self.labels.get(label)
+    }
+
+    /// Current final status of every bundle submitted under `label`, in
+    /// submission order, as reported by `get_bundle_outcomes`.
+    pub async fn outcomes_by_label(
+        &self,
+        label: &str,
+    ) -> Result<Vec<(BundleId, Result<Option<BundleOutcome>, JitoError>)>, JitoError> {
+        self.get_bundle_outcomes(&self.labels.get(label)).await
+    }
+
+    /// Current final status of each of `bundle_ids`, in order, batched into
+    /// as few `getBundleStatuses` calls as the engine allows. Each ID gets
+    /// its own result: `None` when the engine has no final status yet, or an
+    /// error when its status is malformed, reports a failed transaction, or
+    /// the engine rejected the ID. If the engine rejects a batch, its IDs are
+    /// queried one by one so a single bad ID doesn't hide the others. Only
+    /// transport failures fail the whole call.
+    pub async fn get_bundle_outcomes(
+        &self,
+        bundle_ids: &[BundleId],
+    ) -> Result<Vec<(BundleId, Result<Option<BundleOutcome>, JitoError>)>, JitoError> {
+        let mut outcomes = Vec::with_capacity(bundle_ids.len());
+
+        for chunk in bundle_ids.chunks(MAX_STATUS_IDS_PER_REQUEST) {
+            match self.bundle_outcomes_batch(chunk).await {
+                Ok(batch) => outcomes.extend(chunk.iter().cloned().zip(batch)),
+                Err(JitoError::RpcError { .. }) if chunk.len() > 1 => {
+                    for bundle_id in chunk {
+                        let outcome = match self
+                            .bundle_outcomes_batch(std::slice::from_ref(bundle_id))
+                            .await
+                        {
+                            Ok(mut batch) => batch.remove(0),
+                            Err(e @ JitoError::RpcError { .. }) => Err(e),
+                            Err(e) => return Err(e),
+                        };
+                        outcomes.push((bundle_id.clone(), outcome));
+                    }
+                }
+                Err(e @ JitoError::RpcError { .. }) => {
+                    outcomes.push((chunk[0].clone(), Err(e)));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(outcomes)
+    }
+
+    /// In-flight and final status of `bundle_id`. The engine has no call
+    /// returning both, so `getInflightBundleStatuses` and
+    /// `getBundleStatuses` are sent concurrently. Fails like
+    /// `get_bundle_outcomes` does for the final status, and with
+    /// `JitoError::MalformedStatus` on an in-flight status it doesn't know.
+    pub async fn get_combined_status(
+        &self,
+        bundle_id: &BundleId,
+    ) -> Result<CombinedStatus, JitoError> {
+        let ids = [bundle_id];
+        let (inflight, final_status) = tokio::join!(
+            self.status_request(
+                "/getInflightBundleStatuses",
+                "getInflightBundleStatuses",
+                &ids
+            ),
+            self.bundle_outcomes_batch(std::slice::from_ref(bundle_id)),
+        );
+
+        // The engine returns null for IDs it doesn't know.
+        let inflight = match status_entries::<InflightStatusEntry>(&inflight?).pop() {
+            Some(entry) => InflightBundleStatus::parse(&entry.status).ok_or_else(|| {
+                JitoError::MalformedStatus {
+                    bundle_id: bundle_id.clone(),
+                    reason: format!("unknown in-flight status {:?}", entry.status),
+                }
+            })?,
+            None => InflightBundleStatus::Invalid,
+        };
+        let final_status = final_status?.remove(0)?;
+
+        Ok(CombinedStatus {
+            inflight,
+            final_status,
+        })
+    }
+
+    // One `getBundleStatuses` call for at most `MAX_STATUS_IDS_PER_REQUEST`
+    // IDs, with a result per ID in the same order.
+    async fn bundle_outcomes_batch(
+        &self,
+        bundle_ids: &[BundleId],
+    ) -> Result<Vec<Result<Option<BundleOutcome>, JitoError>>, JitoError> {
+        let ids: Vec<&BundleId> = bundle_ids.iter().collect();
+        let response = self
+            .status_request("/getBundleStatuses", "getBundleStatuses", &ids)
+            .await?;
+        let entries = response["value"].as_array().cloned().unwrap_or_default();
+
+        Ok(bundle_ids
+            .iter()
+            .map(|bundle_id| {
+                entries
+                    .iter()
+                    .find(|entry| {
+                        let id = entry.get("bundle_id").or_else(|| entry.get("bundleId"));
+                        id.and_then(Value::as_str) == Some(bundle_id.as_str())
+                    })
+                    .map(|entry| outcome_from_status(bundle_id, entry))
+                    .transpose()
+            })
+            .collect())
+    }
+
+    pub async fn send_txn(
+        &self,
+        params: Option<Value>,
+        bundle_only: bool,
+    ) -> Result<Value, JitoError> {
+        if bundle_only {
+            self.check_tip(params.as_ref()).await?;
+        }
+        let (endpoint, params) = Self::txn_request(params, bundle_only);
+
+        self.send_request(&endpoint, "sendTransaction", Some(params))
+            .await
+    }
+
+    /// Typed variant of `send_txn`. Besides the transaction signature, the
+    /// engine may report the bundle it wrapped the transaction in through the
+    /// `x-bundle-id` response header (notably with `bundle_only`); it is
+    /// returned when present.
+    pub async fn send_txn_typed(
+        &self,
+        params: Option<Value>,
+        bundle_only: bool,
+    ) -> Result<SendTxnResponse, JitoError> {
+        if bundle_only {
+            self.check_tip(params.as_ref()).await?;
+        }
+        let (endpoint, params) = Self::txn_request(params, bundle_only);
+
+        let (headers, body) = self
+            .send_request_with_headers(&endpoint, "sendTransaction", Some(params))
+            .await?;
+
+        let result = rpc_result(body)?;
+        let signature = result
+            .as_str()
+            .and_then(|s| s.parse::<Signature>().ok())
+            .ok_or_else(|| {
+                JitoError::MalformedResponse(format!("invalid transaction signature: {}", result))
+            })?;
+        let bundle_id = headers
+            .get(BUNDLE_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(BundleId::from);
+
+        Ok(SendTxnResponse {
+            signature,
+            bundle_id,
+        })
+    }
+
+    // Applies the configured `TipCheck` to the transaction in `send_txn`
+    // params. A payload that doesn't decode is left for the engine to
+    // reject, and the check is skipped if the tip accounts can't be fetched.
+    async fn check_tip(&self, params: Option<&Value>) -> Result<(), JitoError> {
+        if self.tip_check == TipCheck::Off {
+            return Ok(());
+        }
+        let Some(transaction) = params
+            .and_then(|params| params["tx"].as_str())
+            .and_then(|tx| general_purpose::STANDARD.decode(tx).ok())
+            .and_then(|bytes| bincode::deserialize::<VersionedTransaction>(&bytes).ok())
+        else {
+            return Ok(());
+        };
+
+        let cached = self.last_tip_accounts.lock().unwrap().clone();
+        let tip_accounts = match cached {
+            Some(accounts) => accounts,
+            None => match self.get_tip_accounts_typed().await {
+                Ok(tip_accounts) => tip_accounts.accounts,
+                Err(e) => {
+                    debug!("Skipping tip check, tip accounts unavailable: {}", e);
+                    return Ok(());
+                }
+            },
+        };
+        if pays_tip(&transaction, &tip_accounts) {
+            return Ok(());
+        }
+
+        match self.tip_check {
+            TipCheck::Reject => Err(JitoError::MissingTip),
+            _ => {
+                warn!("Bundle-only transaction pays no tip to a tip account");
+                Ok(())
+            }
+        }
+    }
+
+    fn txn_request(params: Option<Value>, bundle_only: bool) -> (String, Value) {
+        let mut query_params = Vec::new();
+
+        if bundle_only {
+            query_params.push("bundleOnly=true".to_string());
+        }
+
+        let endpoint = if query_params.is_empty() {
+            "/transactions".to_string()
+        } else {
+            format!("/transactions?{}", query_params.join("&"))
+        };
 
         let params = match params {
             Some(Value::Object(map)) => {
@@ -247,26 +1531,1145 @@ impl JitoJsonRpcSDK {
             _ => json!([]),
         };
 
-        self.send_request(&endpoint, "sendTransaction", Some(params))
-            .await
+        (endpoint, params)
     }
 
     pub async fn get_in_flight_bundle_statuses(&self, bundle_uuids: Vec<String>) -> Result<Value> {
-        let endpoint = if let Some(uuid) = &self.uuid {
-            format!("/getInflightBundleStatuses?uuid={}", uuid)
-        } else {
-            "/getInflightBundleStatuses".to_string()
-        };
+        let endpoint = self.endpoint_with_uuid("/getInflightBundleStatuses", None);
 
         let params = json!([bundle_uuids]);
 
         self.send_request(&endpoint, "getInflightBundleStatuses", Some(params))
             .await
-            .map_err(|e| anyhow!("Request error: {}", e))
+            .map_err(Into::into)
+    }
+
+    /// Re-signs `transactions` against a fresh blockhash from the configured
+    /// Solana RPC and submits them as a new bundle, returning its bundle ID.
+    /// This is the usual recovery when a bundle's blockhash expired before it
+    /// landed; every signer of the original transactions must be provided.
+    #[cfg(feature = "solana-rpc")]
+    pub async fn resubmit_with_fresh_blockhash(
+        &self,
+        transactions: &[Transaction],
+        signers: &[&Keypair],
+    ) -> Result<String, JitoError> {
+        let rpc = self
+            .solana_rpc
+            .as_ref()
+            .ok_or(JitoError::SolanaRpcNotConfigured)?;
+        let blockhash = rpc.get_latest_blockhash().await?;
+
+        let mut transactions = transactions.to_vec();
+        sign_bundle_transactions(&mut transactions, signers, blockhash)?;
+
+        self.send_signed_bundle(transactions).await.map(|id| id.0)
+    }
+
+    /// Like `resubmit_with_fresh_blockhash`, but first raises the bundle's
+    /// tip as `escalation` says: to the next tip floor percentile above the
+    /// current tip, up to the cap. The tip is the last system transfer to a
+    /// tip account. Returns the new bundle ID and the tip it pays.
+    #[cfg(feature = "solana-rpc")]
+    pub async fn resubmit_with_escalated_tip(
+        &self,
+        transactions: &[Transaction],
+        signers: &[&Keypair],
+        escalation: &TipEscalation,
+    ) -> Result<Resubmission, JitoError> {
+        let rpc = self
+            .solana_rpc
+            .as_ref()
+            .ok_or(JitoError::SolanaRpcNotConfigured)?;
+        let tip_accounts = self.get_tip_accounts_typed().await?.accounts;
+
+        let mut transactions = transactions.to_vec();
+        let (tx_index, ix_index, current) = transactions
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(i, tx)| {
+                find_tip_transfer(tx, &tip_accounts).map(|(ix, lamports)| (i, ix, lamports))
+            })
+            .ok_or_else(|| {
+                JitoError::InvalidBundle("bundle has no transfer to a tip account".to_string())
+            })?;
+
+        // Without a tip floor the fallback percentage raise still applies.
+        let floor = match self.get_tip_floor().await {
+            Ok(floor) => Some(floor),
+            Err(e) => {
+                warn!("Tip floor unavailable, escalating by percentage: {}", e);
+                None
+            }
+        };
+        let tip_lamports = escalation.next_tip(current, floor.as_ref());
+        debug!(
+            "Escalating tip from {} to {} lamports",
+            current, tip_lamports
+        );
+        set_transfer_lamports(&mut transactions[tx_index], ix_index, tip_lamports);
+
+        let blockhash = rpc.get_latest_blockhash().await?;
+        sign_bundle_transactions(&mut transactions, signers, blockhash)?;
+
+        let bundle_id = self.send_signed_bundle(transactions).await?;
+        Ok(Resubmission {
+            bundle_id,
+            tip_lamports,
+        })
+    }
+
+    async fn send_signed_bundle(
+        &self,
+        transactions: Vec<Transaction>,
+    ) -> Result<BundleId, JitoError> {
+        let tip_lamports = self.capped_bundle_tip(&transactions)?;
+        let mut bundle = BundleBuilder::new();
+        for transaction in transactions {
+            bundle.add_transaction(transaction)?;
+        }
+
+        let endpoint = self.endpoint_with_uuid("/bundles", None);
+        let (headers, body) = self
+            .send_request_with_headers(&endpoint, "sendBundle", Some(bundle.build()?))
+            .await?;
+
+        let bundle_id = bundle_id_from_response(&headers, body)?;
+        self.record_submission(bundle_id.clone());
+        self.tip_spend.record_submitted(tip_lamports);
+        Ok(bundle_id)
+    }
+
+    /// Submits a bundle serialized earlier with `BundleBuilder::prepare`,
+    /// sending its stored request body without serializing anything. Only a
+    /// `jsonrpc_version` override forces the body to be rebuilt.
+    pub async fn send_bundle_prepared(
+        &self,
+        prepared: &PreparedBundle,
+    ) -> Result<BundleId, JitoError> {
+        self.check_params_tip_cap(prepared.params())?;
+        let body = if self.jsonrpc_version == DEFAULT_JSONRPC_VERSION {
+            prepared.body().to_vec()
+        } else {
+            self.request_body("sendBundle", prepared.params().clone())?
+        };
+
+        let endpoint = self.endpoint_with_uuid("/bundles", None);
+        let response = self.send_raw(&endpoint, "sendBundle", body).await?;
+
+        let bundle_id = bundle_id_from_response(&response.headers, response.body)?;
+        self.record_submission(bundle_id.clone());
+        Ok(bundle_id)
+    }
+
+    /// Fire-and-forget `sendBundle`: validates and serializes `transactions`,
+    /// hands the request to a background task and returns without waiting
+    /// for the engine. The bundle ID is never returned to the caller, so
+    /// track the bundle through its transaction signatures or, if enabled,
+    /// `recent_submissions`, which the background task records into.
+    /// It goes to the endpoint that last served a request. Submission
+    /// failures are only logged, and fallback endpoints and connection
+    /// retries are not applied. Must be called within a Tokio
+    /// runtime.
+    pub async fn send_bundle_nowait(&self, transactions: &[Transaction]) -> Result<(), JitoError> {
+        let tip_lamports = self.capped_bundle_tip(transactions)?;
+        let mut bundle = BundleBuilder::new();
+        for transaction in transactions {
+            bundle.add_transaction(transaction.clone())?;
+        }
+        let body = self.request_body("sendBundle", bundle.build()?)?;
+
+        let base_url = self.current_base_url();
+        let url = format!("{}{}", base_url, self.endpoint_with_uuid("/bundles", None));
+        let in_flight = self.in_flight.start();
+        let permit = match &self.connection_limiter {
+            Some(limiter) => Some(limiter.acquire(base_url).await),
+            None => None,
+        };
+        let request = self.build_request(&url, "sendBundle", body);
+        let recent = self.recent_submissions.clone();
+        let tip_spend = self.tip_spend.clone();
+
+        tokio::spawn(async move {
+            let _permit = permit;
+            let _in_flight = in_flight;
+            let response = match request.send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    warn!("Background sendBundle failed: {}", e);
+                    return;
+                }
+            };
+            let headers = response.headers().clone();
+            let bundle_id = match response.json::<Value>().await {
+                Ok(body) => bundle_id_from_response(&headers, body),
+                Err(e) => Err(e.into()),
+            };
+            if bundle_id.is_ok() {
+                tip_spend.record_submitted(tip_lamports);
+            }
+            match (bundle_id, recent) {
+                (Ok(bundle_id), Some(recent)) => recent.record(bundle_id),
+                (Ok(bundle_id), None) => debug!("Background sendBundle accepted: {}", bundle_id),
+                (Err(e), _) => warn!("Background sendBundle failed: {}", e),
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Submits `transactions` as a bundle once a queue slot is free. Under
+    /// contention, queued bundles go out highest `priority` first (e.g. by
+    /// expected profit), with `JitoSdkBuilder::bundle_queue_concurrency`
+    /// bundles in flight at most. The bundle is validated before it queues.
+    pub async fn enqueue_bundle(
+        &self,
+        transactions: Vec<Transaction>,
+        priority: u64,
+    ) -> Result<BundleId, JitoError> {
+        let tip_lamports = self.capped_bundle_tip(&transactions)?;
+        let mut bundle = BundleBuilder::new();
+        for transaction in transactions {
+            bundle.add_transaction(transaction)?;
+        }
+        let params = bundle.build()?;
+
+        let _slot = self.bundle_queue.acquire(priority).await;
+        let endpoint = self.endpoint_with_uuid("/bundles", None);
+        let (headers, body) = self
+            .send_request_with_headers(&endpoint, "sendBundle", Some(params))
+            .await?;
+
+        let bundle_id = bundle_id_from_response(&headers, body)?;
+        self.record_submission(bundle_id.clone());
+        self.tip_spend.record_submitted(tip_lamports);
+        Ok(bundle_id)
+    }
+
+    /// Bundles waiting in `enqueue_bundle` for a queue slot.
+    pub fn bundle_queue_depth(&self) -> usize {
+        self.bundle_queue.depth()
+    }
+
+    fn record_submission(&self, bundle_id: BundleId) {
+        if let Some(recent) = &self.recent_submissions {
+            recent.record(bundle_id);
+        }
+    }
+
+    /// Bundle IDs this client submitted most recently, oldest first, with
+    /// when each was submitted. Empty unless enabled with
+    /// `JitoSdkBuilder::recent_submissions_capacity`.
+    pub fn recent_submissions(&self) -> Vec<(BundleId, Instant)> {
+        self.recent_submissions
+            .as_ref()
+            .map(RecentSubmissions::snapshot)
+            .unwrap_or_default()
+    }
+
+    /// Simulates signed `transactions` as a bundle with `simulateBundle` on
+    /// the configured Solana RPC, which must be a Jito-Solana node. Fails
+    /// with `JitoError::SimulationFailed`, carrying the simulation logs, if
+    /// any transaction fails.
+    #[cfg(feature = "solana-rpc")]
+    pub async fn simulate_bundle(
+        &self,
+        transactions: &[Transaction],
+        opts: &SimulateOptions,
+    ) -> Result<Vec<SimulatedTransaction>, JitoError> {
+        let rpc = self
+            .solana_rpc
+            .as_ref()
+            .ok_or(JitoError::SolanaRpcNotConfigured)?;
+
+        let body = request_body("simulateBundle", simulate_params(transactions, opts)?)?;
+        let response = self
+            .http_client()
+            .post(rpc.url())
+            .header("Content-Type", "application/json")
+            .body(body)
+            .send()
+            .await?
+            .json::<Value>()
+            .await?;
+
+        let simulation: BundleSimulation =
+            serde_json::from_value(rpc_result(response)?["value"].take())
+                .map_err(|e| JitoError::MalformedResponse(format!("simulation result: {}", e)))?;
+        simulation.into_result()
+    }
+
+    /// Simulates `transactions` and submits them as a bundle only if every
+    /// transaction succeeded in simulation.
+    #[cfg(feature = "solana-rpc")]
+    pub async fn simulate_and_send(
+        &self,
+        transactions: &[Transaction],
+        opts: &SimulateOptions,
+    ) -> Result<BundleId, JitoError> {
+        self.simulate_bundle(transactions, opts).await?;
+        self.send_signed_bundle(transactions.to_vec()).await
+    }
+
+    /// Builds, signs and submits a bundle paying `transfers` from `from`,
+    /// with a tip to a random tip account: `tip_lamports`, or the default tip
+    /// when `None` (see `resolve_tip`). Transfers are packed as described in
+    /// `transfer_transactions`. With
+    /// `JitoSdkBuilder::payer_balance_check` the payer's balance is checked
+    /// before submitting.
+    #[cfg(feature = "solana-rpc")]
+    pub async fn send_transfers(
+        &self,
+        from: &Keypair,
+        transfers: &[TransferSpec],
+        tip_lamports: Option<u64>,
+    ) -> Result<BundleId, JitoError> {
+        let rpc = self
+            .solana_rpc
+            .as_ref()
+            .ok_or(JitoError::SolanaRpcNotConfigured)?;
+        let tip_lamports = self.resolve_tip(tip_lamports).await?;
+
+        let tip_accounts = self.get_tip_accounts_typed().await?.accounts;
+        let tip_account = self
+            .pick_tip_account(&tip_accounts)
+            .map(|i| &tip_accounts[i])
+            .ok_or_else(|| JitoError::MalformedResponse("no tip accounts returned".to_string()))?;
+
+        let mut transactions =
+            transfer_transactions(&from.pubkey(), transfers, tip_account, tip_lamports)?;
+        let blockhash = rpc.get_latest_blockhash().await?;
+        sign_bundle_transactions(&mut transactions, &[from], blockhash)?;
+
+        if let Some(reserve) = self.payer_balance_reserve {
+            let mut required = transfers
+                .iter()
+                .fold(tip_lamports, |sum, t| sum.saturating_add(t.lamports))
+                .saturating_add(reserve);
+            for tx in &transactions {
+                required = required.saturating_add(rpc.get_fee_for_message(&tx.message).await?);
+            }
+            let available = rpc.get_balance(&from.pubkey()).await?;
+            if available < required {
+                return Err(JitoError::InsufficientFunds {
+                    required,
+                    available,
+                });
+            }
+        }
+
+        self.send_signed_bundle(transactions).await
+    }
+
+    /// Whether the landed transactions of `outcome` paid at least
+    /// `expected_tip` lamports into tip accounts, read from each
+    /// transaction's own pre- and post-balances on the configured Solana RPC.
+    /// Other bundles tipping the same accounts in the same slot don't affect
+    /// the result, since only these transactions' balance changes count.
+    /// Returns `false` as well when the RPC doesn't have one of the
+    /// transactions, e.g. before it is confirmed or after it was pruned.
+    #[cfg(feature = "solana-rpc")]
+    pub async fn verify_tip_landed(
+        &self,
+        outcome: &BundleOutcome,
+        expected_tip: u64,
+    ) -> Result<bool, JitoError> {
+        let rpc = self
+            .solana_rpc
+            .as_ref()
+            .ok_or(JitoError::SolanaRpcNotConfigured)?;
+        let tip_accounts: Vec<String> = self
+            .get_tip_accounts_typed()
+            .await?
+            .accounts
+            .iter()
+            .map(Pubkey::to_string)
+            .collect();
+
+        let mut tipped = 0u64;
+        for signature in &outcome.transactions {
+            let transaction: Value = rpc
+                .send(
+                    RpcRequest::GetTransaction,
+                    json!([
+                        signature.to_string(),
+                        {
+                            "encoding": "json",
+                            "commitment": "confirmed",
+                            "maxSupportedTransactionVersion": 0
+                        }
+                    ]),
+                )
+                .await?;
+            if transaction.is_null() {
+                return Ok(false);
+            }
+            tipped = tipped.saturating_add(tip_received(&transaction, &tip_accounts));
+        }
+
+        Ok(tipped >= expected_tip)
+    }
+
+    /// Waits until the configured Solana RPC reports `target_slot` or later,
+    /// returning immediately if it already has. Sleeps are sized assuming
+    /// ~400ms slots: far from the target it sleeps for all but the last
+    /// remaining slot, then polls every quarter slot so slow slots don't
+    /// cause an overshoot.
+    #[cfg(feature = "solana-rpc")]
+    pub async fn wait_for_slot(&self, target_slot: u64) -> Result<(), JitoError> {
+        const SLOT_DURATION: Duration = Duration::from_millis(400);
+
+        let rpc = self
+            .solana_rpc
+            .as_ref()
+            .ok_or(JitoError::SolanaRpcNotConfigured)?;
+
+        loop {
+            let current = rpc.get_slot().await?;
+            if current >= target_slot {
+                return Ok(());
+            }
+            let remaining = target_slot - current;
+            trace!(
+                "Waiting for slot {}, {} slots to go",
+                target_slot,
+                remaining
+            );
+            let wait = if remaining > 1 {
+                SLOT_DURATION.saturating_mul((remaining - 1).min(u32::MAX as u64) as u32)
+            } else {
+                SLOT_DURATION / 4
+            };
+            sleep(wait).await;
+        }
+    }
+
+    /// Runs the usual competitive submission flow in one call: prices the
+    /// tip from the tip floor, adds the configured compute budget
+    /// instructions to every transaction and the tip to the last one, signs
+    /// against a fresh blockhash, submits and confirms. Each instruction
+    /// group becomes one transaction, paid by the first of `signers`.
+    #[cfg(feature = "solana-rpc")]
+    pub async fn submit_competitive(
+        &self,
+        instruction_groups: Vec<Vec<Instruction>>,
+        signers: &[&Keypair],
+        config: &CompetitiveConfig,
+    ) -> Result<BundleOutcome, JitoError> {
+        let rpc = self
+            .solana_rpc
+            .as_ref()
+            .ok_or(JitoError::SolanaRpcNotConfigured)?;
+        let payer = signers
+            .first()
+            .ok_or_else(|| JitoError::InvalidBundle("a payer signer is required".to_string()))?
+            .pubkey();
+        if instruction_groups.is_empty() {
+            return Err(JitoError::InvalidBundle(
+                "Bundle must contain at least one transaction".to_string(),
+            ));
+        }
+
+        let submission = async {
+            let tip_lamports = config.capped_tip(
+                self.recommended_tip_or_default(
+                    config.tip_percentile,
+                    config.fallback_tip_lamports,
+                )
+                .await,
+            );
+            let tip_accounts = self.get_tip_accounts_typed().await?.accounts;
+            let tip_account = self
+                .pick_tip_account(&tip_accounts)
+                .map(|i| &tip_accounts[i])
+                .ok_or_else(|| {
+                    JitoError::MalformedResponse("no tip accounts returned".to_string())
+                })?;
+
+            let last = instruction_groups.len() - 1;
+            let groups = instruction_groups
+                .into_iter()
+                .enumerate()
+                .map(|(i, instructions)| {
+                    let mut group = config.compute_budget_ixs();
+                    group.extend(instructions);
+                    if i == last {
+                        group.push(tip_ix(&payer, tip_account, tip_lamports));
+                    }
+                    group
+                })
+                .collect();
+
+            let blockhash = rpc.get_latest_blockhash().await?;
+            let bundle =
+                BundleBuilder::from_instruction_groups(groups, &payer, signers, blockhash)?;
+            let bundle_id = self
+                .send_signed_bundle(bundle.transactions().to_vec())
+                .await?;
+            Ok::<_, JitoError>((bundle_id, tip_lamports))
+        };
+        let (bundle_id, tip_lamports) =
+            within_deadline(config.confirm.operation_deadline, "submission", submission).await?;
+
+        let result = self
+            .confirm_bundles(vec![bundle_id], &config.confirm)
+            .await
+            .pop()
+            .map(|(_, result)| result)
+            .expect("one result per bundle ID");
+        self.record_tip_spend_outcome(tip_lamports, &result);
+        result
+    }
+
+    /// Confirms many bundles with shared polling rounds. Each round queries
+    /// in-flight status for bundles that haven't landed and final status for
+    /// those that have, batching IDs per request instead of polling every
+    /// bundle independently. Results are returned in input order.
+    ///
+    /// A `Failed` in-flight status or a transaction error ends that bundle with
+    /// `JitoError::BundleFailed`. `Invalid` is treated as transient and polled
+    /// again, unless the bundle was already seen `Pending`: the engine has
+    /// then dropped it without landing, which is terminal and ends it with
+    /// `JitoError::BundleDropped`. Bundles still unresolved after
+    /// `max_retries` rounds end with `JitoError::ConfirmationTimeout`, which
+    /// carries the last in-flight status seen so callers can decide whether
+    /// to keep waiting.
+    pub async fn confirm_bundles(
+        &self,
+        bundle_ids: Vec<BundleId>,
+        opts: &ConfirmOptions,
+    ) -> Vec<(BundleId, Result<BundleOutcome, JitoError>)> {
+        self.confirm_bundles_with_events(bundle_ids, opts, |_, _| {})
+            .await
+    }
+
+    /// Confirms one bundle like `confirm_bundles`: polls in-flight status
+    /// until it lands, then final status until it reaches
+    /// `opts.commitment`.
+    pub async fn confirm_bundle(
+        &self,
+        bundle_id: &str,
+        opts: &ConfirmOptions,
+    ) -> Result<BundleConfirmation, JitoError> {
+        self.confirm_bundles(vec![BundleId::from(bundle_id)], opts)
+            .await
+            .pop()
+            .map(|(_, result)| result)
+            .expect("one result per bundle ID")
+    }
+
+    /// Submits signed `transactions` as a bundle and resolves once it lands
+    /// at `opts.commitment`, or fails or times out as `confirm_bundles`
+    /// describes. `opts.operation_deadline` bounds submission and
+    /// confirmation together.
+    pub async fn send_and_await_land(
+        &self,
+        transactions: &[Transaction],
+        opts: &ConfirmOptions,
+    ) -> Result<BundleOutcome, JitoError> {
+        let bundle_id = within_deadline(
+            opts.operation_deadline,
+            "submission",
+            self.send_signed_bundle(transactions.to_vec()),
+        )
+        .await?;
+        let result = self
+            .confirm_bundles(vec![bundle_id], opts)
+            .await
+            .pop()
+            .map(|(_, result)| result)
+            .expect("one result per bundle ID");
+        self.record_tip_spend_outcome(self.bundle_tip_lamports(transactions), &result);
+        result
+    }
+
+    /// Submits signed `transactions` as a bundle and confirms it like
+    /// `confirm_bundles`, calling `on_transition` with every change of the
+    /// bundle's state. The callback runs on a blocking thread fed by a
+    /// channel, so a slow callback delays event delivery but never polling;
+    /// events may still be delivered after this returns.
+    pub async fn send_and_track(
+        &self,
+        transactions: &[Transaction],
+        opts: &ConfirmOptions,
+        mut on_transition: impl FnMut(BundleEvent) + Send + 'static,
+    ) -> Result<BundleOutcome, JitoError> {
+        let (events, received) = std::sync::mpsc::channel();
+        tokio::task::spawn_blocking(move || received.iter().for_each(&mut on_transition));
+
+        let bundle_id = within_deadline(
+            opts.operation_deadline,
+            "submission",
+            self.send_signed_bundle(transactions.to_vec()),
+        )
+        .await?;
+        let _ = events.send(BundleEvent::Submitted(bundle_id.clone()));
+
+        let result = self
+            .confirm_bundles_with_events(vec![bundle_id], opts, |_, event| {
+                let _ = events.send(event);
+            })
+            .await
+            .pop()
+            .map(|(_, result)| result)
+            .expect("one result per bundle ID");
+        self.record_tip_spend_outcome(self.bundle_tip_lamports(transactions), &result);
+        result
+    }
+
+    // `confirm_bundles`, reporting state changes as (bundle index, event).
+    async fn confirm_bundles_with_events(
+        &self,
+        bundle_ids: Vec<BundleId>,
+        opts: &ConfirmOptions,
+        mut on_event: impl FnMut(usize, BundleEvent),
+    ) -> Vec<(BundleId, Result<BundleOutcome, JitoError>)> {
+        let mut results: Vec<Option<Result<BundleOutcome, JitoError>>> =
+            bundle_ids.iter().map(|_| None).collect();
+        let mut landed = vec![false; bundle_ids.len()];
+        let mut landed_slots: Vec<Option<u64>> = vec![None; bundle_ids.len()];
+        let mut last_status: Vec<Option<InflightBundleStatus>> = vec![None; bundle_ids.len()];
+        let mut last_commitment: Vec<Option<CommitmentLevel>> = vec![None; bundle_ids.len()];
+
+        let deadline = opts.operation_deadline;
+        let mut deadline_passed = false;
+        let started = Instant::now();
+        let mut attempts = 0;
+
+        for attempt in 1..=opts.max_retries {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                deadline_passed = true;
+                break;
+            }
+            attempts = attempt;
+            debug!(
+                "Checking status of {} bundles (attempt {}/{})",
+                results.iter().filter(|r| r.is_none()).count(),
+                attempt,
+                opts.max_retries
+            );
+
+            let in_flight: Vec<usize> = (0..bundle_ids.len())
+                .filter(|&i| results[i].is_none() && !landed[i])
+                .collect();
+            for chunk in in_flight.chunks(MAX_STATUS_IDS_PER_REQUEST) {
+                let ids: Vec<&BundleId> = chunk.iter().map(|&i| &bundle_ids[i]).collect();
+                let request = self.status_request(
+                    "/getInflightBundleStatuses",
+                    "getInflightBundleStatuses",
+                    &ids,
+                );
+                let response = match within_deadline(deadline, "confirmation", request).await {
+                    Ok(response) => response,
+                    Err(e) => {
+                        warn!("Error checking in-flight bundle statuses: {}", e);
+                        continue;
+                    }
+                };
+
+                for entry in status_entries::<InflightStatusEntry>(&response) {
+                    let Some(i) = chunk
+                        .iter()
+                        .copied()
+                        .find(|&i| bundle_ids[i] == entry.bundle_id)
+                    else {
+                        continue;
+                    };
+                    let status = InflightBundleStatus::parse(&entry.status);
+                    // `Invalid` means the engine doesn't know the ID within its
+                    // lookback window. Right after submission that's transient,
+                    // but once the bundle was seen pending it has been dropped.
+                    let dropped = status == Some(InflightBundleStatus::Invalid)
+                        && last_status[i] == Some(InflightBundleStatus::Pending);
+                    if let Some(status) = status.filter(|&status| last_status[i] != Some(status)) {
+                        last_status[i] = Some(status);
+                        on_event(i, BundleEvent::InFlight(status));
+                    }
+                    match status {
+                        Some(InflightBundleStatus::Landed) => {
+                            landed[i] = true;
+                            landed_slots[i] = entry.landed_slot;
+                        }
+                        Some(InflightBundleStatus::Failed) => {
+                            results[i] = Some(Err(JitoError::BundleFailed {
+                                bundle_id: bundle_ids[i].clone(),
+                                reason: "in-flight status returned Failed".to_string(),
+                            }));
+                        }
+                        Some(_) if dropped => {
+                            results[i] = Some(Err(JitoError::BundleDropped {
+                                bundle_id: bundle_ids[i].clone(),
+                            }));
+                        }
+                        Some(status) => debug!("Bundle {} is {:?}", bundle_ids[i], status),
+                        None => warn!("Unable to parse status of bundle {}", bundle_ids[i]),
+                    }
+                }
+            }
+
+            let awaiting_final: Vec<usize> = (0..bundle_ids.len())
+                .filter(|&i| results[i].is_none() && landed[i])
+                .collect();
+            for chunk in awaiting_final.chunks(MAX_STATUS_IDS_PER_REQUEST) {
+                let ids: Vec<&BundleId> = chunk.iter().map(|&i| &bundle_ids[i]).collect();
+                let request = self.status_request("/getBundleStatuses", "getBundleStatuses", &ids);
+                let response = match within_deadline(deadline, "confirmation", request).await {
+                    Ok(response) => response,
+                    Err(e) => {
+                        warn!("Error checking final bundle statuses: {}", e);
+                        continue;
+                    }
+                };
+
+                for entry in status_entries::<BundleStatusEntry>(&response) {
+                    let Some(i) = chunk
+                        .iter()
+                        .copied()
+                        .find(|&i| bundle_ids[i] == entry.bundle_id)
+                    else {
+                        continue;
+                    };
+                    if entry.has_error() {
+                        results[i] = Some(Err(JitoError::BundleFailed {
+                            bundle_id: bundle_ids[i].clone(),
+                            reason: format!("transaction error: {}", entry.err),
+                        }));
+                        continue;
+                    }
+                    let mut outcome = match BundleOutcome::from_status_entry(entry) {
+                        Ok(outcome) => outcome,
+                        Err(e) => {
+                            results[i] = Some(Err(e));
+                            continue;
+                        }
+                    };
+                    outcome.landed_slot = outcome.landed_slot.or(landed_slots[i]);
+                    if let Some(level) = outcome
+                        .confirmation_status
+                        .filter(|&level| last_commitment[i] != Some(level))
+                    {
+                        last_commitment[i] = Some(level);
+                        on_event(i, BundleEvent::Committed(level));
+                    }
+                    if outcome
+                        .confirmation_status
+                        .is_some_and(|status| status >= opts.commitment)
+                    {
+                        results[i] = Some(Ok(outcome));
+                    }
+                }
+            }
+
+            if results.iter().all(Option::is_some) {
+                break;
+            }
+            if attempt < opts.max_retries {
+                let mut delay = opts.delay(self.rtt.estimate());
+                if let Some(deadline) = deadline {
+                    delay = delay.min(deadline.saturating_duration_since(Instant::now()));
+                }
+                sleep(delay).await;
+            }
+        }
+        deadline_passed |= deadline.is_some_and(|deadline| Instant::now() >= deadline);
+
+        let elapsed = started.elapsed();
+
+        bundle_ids
+            .into_iter()
+            .zip(results)
+            .zip(last_status)
+            .map(|((id, result), last_status)| {
+                let result = result.unwrap_or_else(|| {
+                    Err(if deadline_passed {
+                        JitoError::DeadlineExceeded {
+                            step: "confirmation",
+                        }
+                    } else {
+                        JitoError::ConfirmationTimeout {
+                            last_status,
+                            attempts,
+                            elapsed,
+                        }
+                    })
+                });
+                (id, result)
+            })
+            .collect()
+    }
+
+    async fn status_request(
+        &self,
+        path: &str,
+        method: &str,
+        bundle_ids: &[&BundleId],
+    ) -> Result<Value, JitoError> {
+        let endpoint = self.endpoint_with_uuid(path, None);
+        let ids: Vec<&str> = bundle_ids.iter().map(|id| id.as_str()).collect();
+
+        let response = self
+            .send_request(&endpoint, method, Some(json!([ids])))
+            .await?;
+        rpc_result(response)
     }
 
     // Helper method
     pub fn prettify(value: Value) -> PrettyJsonValue {
         PrettyJsonValue(value)
     }
+
+    /// Single-line JSON for structured log pipelines, as opposed to the
+    /// indented output of `prettify`.
+    pub fn prettify_compact(value: &Value) -> String {
+        value.to_string()
+    }
+}
+
+// Runs `future` unless `deadline` passes first, failing with
+// `JitoError::DeadlineExceeded` for `step` if it does.
+async fn within_deadline<T>(
+    deadline: Option<Instant>,
+    step: &'static str,
+    future: impl std::future::Future<Output = Result<T, JitoError>>,
+) -> Result<T, JitoError> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline.into(), future)
+            .await
+            .map_err(|_| JitoError::DeadlineExceeded { step })?,
+        None => future.await,
+    }
+}
+
+// Reads the best-effort bundle ID header and warns when it disagrees with the
+// bundle ID in the response body.
+fn check_bundle_id_header(headers: &HeaderMap, body: &Value) -> Option<BundleId> {
+    let header_id = headers
+        .get(BUNDLE_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(BundleId::from)?;
+
+    if let Some(body_id) = body["result"].as_str() {
+        if body_id != header_id.as_str() {
+            warn!(
+                "Bundle ID mismatch: body has {}, {} header has {}",
+                body_id, BUNDLE_ID_HEADER, header_id
+            );
+        }
+    }
+    Some(header_id)
+}
+
+// `sendBundle` params from what `send_bundle` accepts: either complete
+// `[transactions, options]` params, used as is, or a bare array of encoded
+// transactions, sent with the default options.
+fn bundle_request_params(params: Option<Value>) -> Result<Value, JitoError> {
+    match params {
+        Some(value) if value.as_array().is_some_and(|params| params.len() == 2) => Ok(value),
+        Some(Value::Array(transactions)) => {
+            if transactions.is_empty() {
+                return Err(JitoError::InvalidBundle(
+                    "Bundle must contain at least one transaction".to_string(),
+                ));
+            }
+            if transactions.len() > MAX_BUNDLE_TRANSACTIONS {
+                return Err(JitoError::InvalidBundle(format!(
+                    "Bundle can contain at most {} transactions",
+                    MAX_BUNDLE_TRANSACTIONS
+                )));
+            }
+
+            Ok(json!([transactions, BundleOptions::default()]))
+        }
+        _ => Err(JitoError::InvalidBundle(
+            "expected an array of transactions".to_string(),
+        )),
+    }
+}
+
+// Extracts the bundle ID from a sendBundle response. The body's `result` is
+// authoritative; the header is only used when the body carries no ID.
+fn bundle_id_from_response(headers: &HeaderMap, body: Value) -> Result<BundleId, JitoError> {
+    let header_id = check_bundle_id_header(headers, &body);
+
+    match body.get("result") {
+        Some(Value::String(id)) => Ok(BundleId(id.clone())),
+        Some(Value::Null) | None if body.get("error").is_none() => header_id
+            .ok_or_else(|| JitoError::MalformedResponse("response has no bundle ID".to_string())),
+        _ => rpc_result(body).and_then(|result| {
+            Err(JitoError::MalformedResponse(format!(
+                "bundle ID is not a string: {}",
+                result
+            )))
+        }),
+    }
+}
+
+fn outcome_from_status(bundle_id: &BundleId, entry: &Value) -> Result<BundleOutcome, JitoError> {
+    let entry = BundleStatusEntry::deserialize(entry).map_err(|e| JitoError::MalformedStatus {
+        bundle_id: bundle_id.clone(),
+        reason: e.to_string(),
+    })?;
+    if entry.has_error() {
+        return Err(JitoError::BundleFailed {
+            bundle_id: bundle_id.clone(),
+            reason: format!("transaction error: {}", entry.err),
+        });
+    }
+    BundleOutcome::from_status_entry(entry)
+}
+
+// Lamports a `getTransaction` result moved into any of `tip_accounts`. Keys
+// loaded from lookup tables follow the static keys, writable ones first,
+// matching the order of the balance arrays.
+#[cfg(feature = "solana-rpc")]
+fn tip_received(transaction: &Value, tip_accounts: &[String]) -> u64 {
+    let meta = &transaction["meta"];
+    let as_array = |value: &Value| value.as_array().cloned().unwrap_or_default();
+    let keys = [
+        as_array(&transaction["transaction"]["message"]["accountKeys"]),
+        as_array(&meta["loadedAddresses"]["writable"]),
+        as_array(&meta["loadedAddresses"]["readonly"]),
+    ]
+    .concat();
+    let pre = as_array(&meta["preBalances"]);
+    let post = as_array(&meta["postBalances"]);
+
+    keys.iter()
+        .enumerate()
+        .filter(|(_, key)| {
+            key.as_str()
+                .is_some_and(|key| tip_accounts.iter().any(|tip| tip == key))
+        })
+        .filter_map(|(i, _)| Some(post.get(i)?.as_u64()?.saturating_sub(pre.get(i)?.as_u64()?)))
+        .fold(0u64, u64::saturating_add)
+}
+
+pub(crate) fn parse_ips(ips: Vec<String>) -> Result<Vec<IpAddr>> {
+    ips.into_iter()
+        .map(|s| {
+            s.parse::<IpAddr>()
+                .map_err(|e| anyhow!("Parse ip failed: {} {}", s, e))
+        })
+        .collect()
+}
+
+// A failed connection means the request never reached the engine, so it is
+// safe to retry for any method. A timeout may hit after the engine accepted
+// the request, so only reads are retried then, never submissions. HTTP error
+// statuses come back as responses and are never retried.
+fn is_retryable(err: &reqwest::Error, method: &str) -> bool {
+    err.is_connect() || (err.is_timeout() && !matches!(method, "sendBundle" | "sendTransaction"))
+}
+
+// `Retry-After` given in seconds. The HTTP-date form is ignored, leaving the
+// exponential backoff to apply.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+// Bodies that aren't JSON, such as a CDN's HTML error page during an outage,
+// are reported with their content type and the start of the body.
+fn unexpected_response(headers: &HeaderMap, body: &[u8]) -> JitoError {
+    const SNIPPET_CHARS: usize = 200;
+
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("none")
+        .to_string();
+    let body = String::from_utf8_lossy(body);
+    let mut snippet: String = body.trim().chars().take(SNIPPET_CHARS).collect();
+    if body.trim().chars().nth(SNIPPET_CHARS).is_some() {
+        snippet.push_str("...");
+    }
+    JitoError::UnexpectedResponse {
+        content_type,
+        snippet,
+    }
+}
+
+// Endpoint path the SDK sends `method` to.
+fn method_path(method: &str) -> &'static str {
+    match method {
+        "sendTransaction" => "/transactions",
+        "getBundleStatuses" => "/getBundleStatuses",
+        "getInflightBundleStatuses" => "/getInflightBundleStatuses",
+        _ => "/bundles",
+    }
+}
+
+const REDACTED: &str = "[redacted]";
+
+fn redact_uuid(url: &str) -> String {
+    match url.split_once("uuid=") {
+        Some((prefix, rest)) => {
+            let tail = rest.find('&').map_or("", |i| &rest[i..]);
+            format!("{}uuid={}{}", prefix, REDACTED, tail)
+        }
+        None => url.to_string(),
+    }
+}
+
+// Single-quotes `value` for POSIX shells; embedded quotes become '\''.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+fn redact_auth_headers(mut headers: HeaderMap) -> HeaderMap {
+    for name in [
+        AUTHORIZATION.as_str(),
+        PROXY_AUTHORIZATION.as_str(),
+        http_client::AUTH_HEADER,
+    ] {
+        if let Some(value) = headers.get_mut(name) {
+            *value = HeaderValue::from_static(REDACTED);
+        }
+    }
+    headers
+}
+
+// Serializes a JSON-RPC request into the exact bytes sent on the wire.
+pub(crate) fn request_body(method: &str, params: Value) -> Result<Vec<u8>, JitoError> {
+    versioned_request_body(DEFAULT_JSONRPC_VERSION, method, params)
+}
+
+fn versioned_request_body(
+    version: &str,
+    method: &str,
+    params: Value,
+) -> Result<Vec<u8>, JitoError> {
+    let data = json!({
+        "jsonrpc": version,
+        "id": 1,
+        "method": method,
+        "params": params
+    });
+
+    serde_json::to_vec(&data).map_err(JitoError::from)
+}
+
+// Unwraps a JSON-RPC response body, turning an `error` object into
+// `JitoError::RpcError`.
+fn rpc_result(body: Value) -> Result<Value, JitoError> {
+    if let Some(error) = body.get("error") {
+        return Err(JitoError::RpcError {
+            code: error["code"].as_i64().unwrap_or_default(),
+            message: error["message"].as_str().unwrap_or_default().to_string(),
+            data: error.get("data").filter(|data| !data.is_null()).cloned(),
+        });
+    }
+
+    match body {
+        Value::Object(mut map) => map
+            .remove("result")
+            .ok_or_else(|| JitoError::MalformedResponse("missing result field".to_string())),
+        _ => Err(JitoError::MalformedResponse(
+            "expected a JSON object".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sdk(uuid: Option<&str>) -> JitoJsonRpcSDK {
+        JitoJsonRpcSDK::new("http://127.0.0.1:1/api/v1", uuid.map(str::to_string))
+    }
+
+    #[test]
+    fn endpoint_without_any_uuid_has_no_query() {
+        assert_eq!(sdk(None).endpoint_with_uuid("/bundles", None), "/bundles");
+    }
+
+    #[test]
+    fn endpoint_uses_per_call_uuid_without_global() {
+        assert_eq!(
+            sdk(None).endpoint_with_uuid("/bundles", Some("call")),
+            "/bundles?uuid=call"
+        );
+    }
+
+    #[test]
+    fn endpoint_uses_global_uuid_without_per_call() {
+        assert_eq!(
+            sdk(Some("global")).endpoint_with_uuid("/bundles", None),
+            "/bundles?uuid=global"
+        );
+    }
+
+    #[test]
+    fn per_call_uuid_overrides_global() {
+        assert_eq!(
+            sdk(Some("global")).endpoint_with_uuid("/bundles", Some("call")),
+            "/bundles?uuid=call"
+        );
+    }
+
+    #[test]
+    fn landed_bundle_without_transactions_is_malformed() {
+        let bundle_id = BundleId::from("abc");
+        let entry = json!({
+            "bundle_id": "abc",
+            "slot": 42,
+            "confirmation_status": "confirmed",
+            "transactions": [],
+            "err": {"Ok": null},
+        });
+
+        match outcome_from_status(&bundle_id, &entry) {
+            Err(JitoError::MalformedStatus { bundle_id, reason }) => {
+                assert_eq!(bundle_id, BundleId::from("abc"));
+                assert_eq!(reason, "landed bundle lists no transactions");
+            }
+            other => panic!("expected MalformedStatus, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn landed_bundle_transactions_are_parsed_as_signatures() {
+        let signature = Signature::from([7; 64]);
+        let entry = json!({
+            "bundle_id": "abc",
+            "slot": 42,
+            "transactions": [signature.to_string()],
+            "err": {"Ok": null},
+        });
+
+        let outcome = outcome_from_status(&BundleId::from("abc"), &entry).unwrap();
+        assert_eq!(outcome.transactions, vec![signature]);
+    }
+
+    #[tokio::test]
+    async fn multi_region_submit_waits_for_a_region_slot() {
+        let sdk = JitoJsonRpcSDK::builder("http://127.0.0.1:1/api/v1")
+            .max_connections_per_region(1)
+            .build()
+            .unwrap();
+        let regions = [JitoRegion::Frankfurt];
+        let held = sdk
+            .connection_limiter
+            .as_ref()
+            .unwrap()
+            .acquire(regions[0].base_url())
+            .await;
+
+        let mut send =
+            Box::pin(sdk.send_bundle_multi_region(Some(json!(["AQ=="])), &regions, None));
+        let waited = tokio::time::timeout(Duration::from_millis(100), &mut send).await;
+
+        assert!(waited.is_err(), "submission went out without a slot");
+        assert_eq!(sdk.in_flight_requests(), 1);
+        drop(send);
+        drop(held);
+        assert_eq!(sdk.in_flight_requests(), 0);
+    }
 }
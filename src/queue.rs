@@ -0,0 +1,168 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+/// Submission slots handed out by priority, shared across clones. At most
+/// `slots` are held at once; when one is released it goes to the waiter with
+/// the highest priority, first come first served among equal priorities.
+#[derive(Debug, Clone)]
+pub struct BundleQueue {
+    inner: Arc<Mutex<QueueInner>>,
+}
+
+#[derive(Debug)]
+struct QueueInner {
+    available: usize,
+    next_seq: u64,
+    waiters: BinaryHeap<Waiter>,
+}
+
+#[derive(Debug)]
+struct Waiter {
+    priority: u64,
+    seq: u64,
+    ready: oneshot::Sender<QueueSlot>,
+}
+
+// Highest priority first, then lowest sequence number.
+impl Ord for Waiter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+/// A held submission slot, released to the next waiter when dropped.
+#[derive(Debug)]
+pub struct QueueSlot {
+    queue: BundleQueue,
+}
+
+impl Drop for QueueSlot {
+    fn drop(&mut self) {
+        self.queue.release();
+    }
+}
+
+impl BundleQueue {
+    /// Queue handing out `slots` slots at once.
+    ///
+    /// # Panics
+    ///
+    /// If `slots` is 0, since no `acquire` could ever complete.
+    pub fn new(slots: usize) -> Self {
+        assert!(slots > 0, "a bundle queue needs at least one slot");
+        Self {
+            inner: Arc::new(Mutex::new(QueueInner {
+                available: slots,
+                next_seq: 0,
+                waiters: BinaryHeap::new(),
+            })),
+        }
+    }
+
+    /// Waits for a slot. Nobody jumps the queue: a free slot is only taken
+    /// directly when no one else is waiting.
+    pub async fn acquire(&self, priority: u64) -> QueueSlot {
+        let ready = {
+            let mut inner = self.inner.lock().unwrap();
+            if inner.available > 0 && inner.waiters.is_empty() {
+                inner.available -= 1;
+                return QueueSlot {
+                    queue: self.clone(),
+                };
+            }
+            let (ready, slot) = oneshot::channel();
+            let seq = inner.next_seq;
+            inner.next_seq += 1;
+            inner.waiters.push(Waiter {
+                priority,
+                seq,
+                ready,
+            });
+            slot
+        };
+        // Waiters are only dropped by `release`, after sending them a slot.
+        ready.await.expect("bundle queue dropped a waiter")
+    }
+
+    /// Bundles waiting for a slot. Waiters that gave up are not counted,
+    /// though they are only removed once a slot is released.
+    pub fn depth(&self) -> usize {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .waiters
+            .iter()
+            .filter(|waiter| !waiter.ready.is_closed())
+            .count()
+    }
+
+    fn release(&self) {
+        let waiter = {
+            let mut inner = self.inner.lock().unwrap();
+            match inner.waiters.pop() {
+                Some(waiter) => waiter,
+                None => {
+                    inner.available += 1;
+                    return;
+                }
+            }
+        };
+        // If the waiter gave up, the slot comes back and is dropped here,
+        // which releases it to the next waiter.
+        let _ = waiter.ready.send(QueueSlot {
+            queue: self.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "at least one slot")]
+    fn zero_slots_are_rejected() {
+        BundleQueue::new(0);
+    }
+
+    #[tokio::test]
+    async fn released_slot_goes_to_the_highest_priority_waiter() {
+        let queue = BundleQueue::new(1);
+        let held = queue.acquire(0).await;
+
+        let low = tokio::spawn({
+            let queue = queue.clone();
+            async move { queue.acquire(1).await }
+        });
+        let high = tokio::spawn({
+            let queue = queue.clone();
+            async move { queue.acquire(5).await }
+        });
+        while queue.depth() < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        drop(held);
+        let slot = high.await.unwrap();
+        assert_eq!(queue.depth(), 1);
+        drop(slot);
+        low.await.unwrap();
+    }
+}
@@ -0,0 +1,199 @@
+use std::time::Duration;
+
+use crate::error::JitoError;
+
+/// Jito block engine regions, each served from its own base URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JitoRegion {
+    /// Global endpoint, routed to a nearby region.
+    Mainnet,
+    Amsterdam,
+    Frankfurt,
+    NewYork,
+    Tokyo,
+    SaltLakeCity,
+}
+
+impl JitoRegion {
+    pub const ALL: [JitoRegion; 6] = [
+        JitoRegion::Mainnet,
+        JitoRegion::Amsterdam,
+        JitoRegion::Frankfurt,
+        JitoRegion::NewYork,
+        JitoRegion::Tokyo,
+        JitoRegion::SaltLakeCity,
+    ];
+
+    pub fn base_url(&self) -> &'static str {
+        match self {
+            JitoRegion::Mainnet => "https://mainnet.block-engine.jito.wtf/api/v1",
+            JitoRegion::Amsterdam => "https://amsterdam.mainnet.block-engine.jito.wtf/api/v1",
+            JitoRegion::Frankfurt => "https://frankfurt.mainnet.block-engine.jito.wtf/api/v1",
+            JitoRegion::NewYork => "https://ny.mainnet.block-engine.jito.wtf/api/v1",
+            JitoRegion::Tokyo => "https://tokyo.mainnet.block-engine.jito.wtf/api/v1",
+            JitoRegion::SaltLakeCity => "https://slc.mainnet.block-engine.jito.wtf/api/v1",
+        }
+    }
+
+    /// The region whose `base_url` is `url`, ignoring a trailing slash.
+    pub fn from_base_url(url: &str) -> Option<JitoRegion> {
+        let url = url.trim_end_matches('/');
+        Self::ALL
+            .into_iter()
+            .find(|region| region.base_url() == url)
+    }
+}
+
+/// Latency of one configured endpoint, from
+/// `JitoJsonRpcSDK::measure_region_latencies`.
+#[derive(Debug)]
+pub struct RegionLatency {
+    pub base_url: String,
+    /// `None` if `base_url` isn't one of `JitoRegion`'s.
+    pub region: Option<JitoRegion>,
+    pub latency: Result<Duration, JitoError>,
+}
+
+/// Settings for `JitoJsonRpcSDK::spawn_region_switching`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegionSwitching {
+    /// Time between latency measurements.
+    pub interval: Duration,
+    /// How much faster another endpoint must be than the active one.
+    pub margin: Duration,
+    /// Measurements in a row the same endpoint must win by `margin` before
+    /// requests move to it.
+    pub consecutive_rounds: u32,
+}
+
+impl Default for RegionSwitching {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            margin: Duration::from_millis(20),
+            consecutive_rounds: 3,
+        }
+    }
+}
+
+// Tracks which endpoint has been consistently faster than the active one.
+#[derive(Debug, Default)]
+pub(crate) struct RegionSwitcher {
+    candidate: Option<usize>,
+    rounds: u32,
+}
+
+impl RegionSwitcher {
+    /// Feeds one measurement, `None` for endpoints that failed, and returns
+    /// the endpoint to switch to once one has won often enough.
+    pub fn observe(
+        &mut self,
+        active: usize,
+        latencies: &[Option<Duration>],
+        config: &RegionSwitching,
+    ) -> Option<usize> {
+        let fastest = latencies
+            .iter()
+            .enumerate()
+            .filter_map(|(i, latency)| Some((i, (*latency)?)))
+            .min_by_key(|&(_, latency)| latency);
+        let leader = fastest.filter(|&(i, latency)| {
+            i != active
+                && latencies
+                    .get(active)
+                    .copied()
+                    .flatten()
+                    .is_none_or(|active| active > latency + config.margin)
+        });
+
+        let Some((leader, _)) = leader else {
+            *self = Self::default();
+            return None;
+        };
+        if self.candidate == Some(leader) {
+            self.rounds += 1;
+        } else {
+            self.candidate = Some(leader);
+            self.rounds = 1;
+        }
+        if self.rounds < config.consecutive_rounds {
+            return None;
+        }
+        *self = Self::default();
+        Some(leader)
+    }
+}
+
+/// Solana cluster a block engine serves, which decides the static tip
+/// account set used when `getTipAccounts` can't be reached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Testnet,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    fn config(consecutive_rounds: u32) -> RegionSwitching {
+        RegionSwitching {
+            margin: 10 * MS,
+            consecutive_rounds,
+            ..RegionSwitching::default()
+        }
+    }
+
+    #[test]
+    fn regions_round_trip_through_base_url() {
+        for region in JitoRegion::ALL {
+            assert_eq!(JitoRegion::from_base_url(region.base_url()), Some(region));
+        }
+        let with_slash = format!("{}/", JitoRegion::Tokyo.base_url());
+        assert_eq!(
+            JitoRegion::from_base_url(&with_slash),
+            Some(JitoRegion::Tokyo)
+        );
+        assert_eq!(JitoRegion::from_base_url("http://localhost:8080"), None);
+    }
+
+    #[test]
+    fn switches_after_consecutive_wins() {
+        let mut switcher = RegionSwitcher::default();
+        let latencies = [Some(80 * MS), Some(30 * MS)];
+        assert_eq!(switcher.observe(0, &latencies, &config(3)), None);
+        assert_eq!(switcher.observe(0, &latencies, &config(3)), None);
+        assert_eq!(switcher.observe(0, &latencies, &config(3)), Some(1));
+    }
+
+    #[test]
+    fn stays_within_margin() {
+        let mut switcher = RegionSwitcher::default();
+        let latencies = [Some(35 * MS), Some(30 * MS)];
+        assert_eq!(switcher.observe(0, &latencies, &config(1)), None);
+    }
+
+    #[test]
+    fn a_lost_lead_restarts_the_count() {
+        let mut switcher = RegionSwitcher::default();
+        let faster = [Some(80 * MS), Some(30 * MS)];
+        let even = [Some(30 * MS), Some(30 * MS)];
+        assert_eq!(switcher.observe(0, &faster, &config(2)), None);
+        assert_eq!(switcher.observe(0, &even, &config(2)), None);
+        assert_eq!(switcher.observe(0, &faster, &config(2)), None);
+        assert_eq!(switcher.observe(0, &faster, &config(2)), Some(1));
+    }
+
+    #[test]
+    fn leaves_a_failing_endpoint() {
+        let mut switcher = RegionSwitcher::default();
+        assert_eq!(
+            switcher.observe(0, &[None, Some(200 * MS)], &config(1)),
+            Some(1)
+        );
+        assert_eq!(switcher.observe(0, &[None, None], &config(1)), None);
+    }
+}
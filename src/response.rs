@@ -0,0 +1,82 @@
+use reqwest::{header::HeaderMap, StatusCode};
+use serde_json::Value;
+use solana_signature::Signature;
+use std::time::Duration;
+
+use crate::bundle::BundleId;
+use crate::region::JitoRegion;
+
+/// Result of `send_txn_typed`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SendTxnResponse {
+    pub signature: Signature,
+    /// Bundle the engine wrapped the transaction in, when it reports one.
+    pub bundle_id: Option<BundleId>,
+}
+
+/// Result of `send_bundle_detailed` and `send_bundle_multi_region`: the
+/// bundle ID and the endpoint that accepted the bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SendBundleResponse {
+    pub bundle_id: BundleId,
+    /// Region of `base_url`, `None` for endpoints outside `JitoRegion`.
+    pub region: Option<JitoRegion>,
+    pub base_url: String,
+}
+
+/// Result of `resubmit_with_escalated_tip`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resubmission {
+    pub bundle_id: BundleId,
+    /// Tip the resubmitted bundle pays.
+    pub tip_lamports: u64,
+}
+
+/// What was sent and received by `call_verbose`. The UUID and any auth
+/// headers are redacted.
+#[derive(Debug, Clone)]
+pub struct RequestDebug {
+    pub url: String,
+    pub request_body: String,
+    pub status: StatusCode,
+    pub response_headers: HeaderMap,
+    pub rtt: Duration,
+    /// Requests sent, counting connection retries and endpoint failover. 1
+    /// when the first attempt went through.
+    pub attempts: u32,
+}
+
+/// Detail the engine attaches to some JSON-RPC errors in their `data`
+/// member, such as which transaction of a bundle was rejected and the logs
+/// of a failed simulation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RpcErrorData {
+    pub transaction_index: Option<usize>,
+    pub err: Option<Value>,
+    pub logs: Vec<String>,
+}
+
+impl RpcErrorData {
+    /// Reads the known fields, accepting snake_case or camelCase. Returns
+    /// `None` when `data` is not an object or has none of them.
+    pub fn parse(data: &Value) -> Option<Self> {
+        let field = |snake: &str, camel: &str| data.get(snake).or_else(|| data.get(camel));
+
+        let parsed = Self {
+            transaction_index: field("transaction_index", "transactionIndex")
+                .and_then(Value::as_u64)
+                .map(|index| index as usize),
+            err: data.get("err").filter(|err| !err.is_null()).cloned(),
+            logs: data
+                .get("logs")
+                .and_then(Value::as_array)
+                .map(|logs| {
+                    logs.iter()
+                        .filter_map(|line| line.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+        (parsed != Self::default()).then_some(parsed)
+    }
+}
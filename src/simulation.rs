@@ -0,0 +1,83 @@
+use base64::{engine::general_purpose, Engine as _};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_transaction::Transaction;
+
+use crate::error::JitoError;
+
+/// Options for `simulate_bundle`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SimulateOptions {
+    pub skip_sig_verify: bool,
+    /// Simulate against the node's latest blockhash instead of the one the
+    /// transactions were signed with.
+    pub replace_recent_blockhash: bool,
+}
+
+/// Result of simulating one transaction of a bundle.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedTransaction {
+    #[serde(default)]
+    pub err: Option<Value>,
+    #[serde(default)]
+    pub logs: Option<Vec<String>>,
+    #[serde(default)]
+    pub units_consumed: Option<u64>,
+}
+
+// `value` of a `simulateBundle` response. `summary` is either the string
+// "succeeded" or `{"failed": {"error": ..., "tx_signature": ...}}`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BundleSimulation {
+    summary: Value,
+    #[serde(default)]
+    transaction_results: Vec<SimulatedTransaction>,
+}
+
+impl BundleSimulation {
+    /// The per-transaction results, or `JitoError::SimulationFailed` with
+    /// the logs of every simulated transaction if any of them failed.
+    pub(crate) fn into_result(self) -> Result<Vec<SimulatedTransaction>, JitoError> {
+        let failed = &self.summary["failed"];
+        if self.summary == "succeeded" {
+            return Ok(self.transaction_results);
+        }
+
+        let reason = if failed.is_null() {
+            self.summary.to_string()
+        } else {
+            failed["error"].to_string()
+        };
+        let logs = self
+            .transaction_results
+            .into_iter()
+            .flat_map(|result| result.logs.unwrap_or_default())
+            .collect();
+        Err(JitoError::SimulationFailed { reason, logs })
+    }
+}
+
+pub(crate) fn simulate_params(
+    transactions: &[Transaction],
+    opts: &SimulateOptions,
+) -> Result<Value, JitoError> {
+    let encoded = transactions
+        .iter()
+        .map(|tx| Ok(general_purpose::STANDARD.encode(bincode::serialize(tx)?)))
+        .collect::<Result<Vec<_>, JitoError>>()?;
+    let no_accounts = vec![Value::Null; transactions.len()];
+
+    Ok(json!([
+        {
+            "encodedTransactions": encoded
+        },
+        {
+            "preExecutionAccountsConfigs": no_accounts,
+            "postExecutionAccountsConfigs": no_accounts,
+            "skipSigVerify": opts.skip_sig_verify,
+            "replaceRecentBlockhash": opts.replace_recent_blockhash
+        }
+    ]))
+}
@@ -0,0 +1,359 @@
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+use serde_json::Value;
+use solana_commitment_config::{CommitmentConfig, CommitmentLevel as SolanaCommitmentLevel};
+use solana_signature::Signature;
+use solana_transaction_error::TransactionError;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use crate::bundle::{BundleId, BundleOutcome};
+use crate::error::JitoError;
+
+/// Maximum number of bundle IDs the status endpoints accept per request.
+pub const MAX_STATUS_IDS_PER_REQUEST: usize = 5;
+
+/// Commitment level reported by `getBundleStatuses`, ordered from weakest to
+/// strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CommitmentLevel {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl CommitmentLevel {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "processed" => Some(CommitmentLevel::Processed),
+            "confirmed" => Some(CommitmentLevel::Confirmed),
+            "finalized" => Some(CommitmentLevel::Finalized),
+            _ => None,
+        }
+    }
+}
+
+impl From<SolanaCommitmentLevel> for CommitmentLevel {
+    fn from(level: SolanaCommitmentLevel) -> Self {
+        match level {
+            SolanaCommitmentLevel::Processed => CommitmentLevel::Processed,
+            SolanaCommitmentLevel::Confirmed => CommitmentLevel::Confirmed,
+            SolanaCommitmentLevel::Finalized => CommitmentLevel::Finalized,
+        }
+    }
+}
+
+impl From<CommitmentConfig> for CommitmentLevel {
+    fn from(config: CommitmentConfig) -> Self {
+        config.commitment.into()
+    }
+}
+
+/// Status reported by `getInflightBundleStatuses`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InflightBundleStatus {
+    Invalid,
+    Pending,
+    Failed,
+    Landed,
+}
+
+impl InflightBundleStatus {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "Invalid" => Some(InflightBundleStatus::Invalid),
+            "Pending" => Some(InflightBundleStatus::Pending),
+            "Failed" => Some(InflightBundleStatus::Failed),
+            "Landed" => Some(InflightBundleStatus::Landed),
+            _ => None,
+        }
+    }
+}
+
+/// One bundle's entry in a `getBundleStatuses` response, as returned by
+/// `get_bundle_statuses`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleStatus {
+    pub bundle_id: BundleId,
+    pub confirmation_status: Option<CommitmentLevel>,
+    /// Error of the failed transaction, `None` if the bundle succeeded.
+    pub err: Option<TransactionError>,
+    pub transactions: Vec<Signature>,
+    pub slot: Option<u64>,
+}
+
+impl BundleStatus {
+    /// Parses every entry of a `getBundleStatuses` response's
+    /// `result.value`, skipping the nulls the engine returns for unknown
+    /// bundle IDs. Fails with `JitoError::RpcError` for an error response,
+    /// `JitoError::MalformedResponse` if there is no `result.value` array,
+    /// and `JitoError::MalformedStatus` for an entry that doesn't parse.
+    pub fn from_value(response: &Value) -> Result<Vec<Self>, JitoError> {
+        let result = crate::rpc_result(response.clone())?;
+        let entries = result["value"].as_array().ok_or_else(|| {
+            JitoError::MalformedResponse("missing result.value array".to_string())
+        })?;
+
+        entries
+            .iter()
+            .filter(|entry| !entry.is_null())
+            .map(Self::from_entry)
+            .collect()
+    }
+
+    fn from_entry(entry: &Value) -> Result<Self, JitoError> {
+        let malformed = |reason: String| JitoError::MalformedStatus {
+            bundle_id: entry["bundle_id"]
+                .as_str()
+                .or_else(|| entry["bundleId"].as_str())
+                .map(BundleId::from)
+                .unwrap_or_default(),
+            reason,
+        };
+        let entry = BundleStatusEntry::deserialize(entry).map_err(|e| malformed(e.to_string()))?;
+
+        // Success is reported as `{"Ok": null}`, failure as `{"Err": ...}`.
+        let err = match entry.err.get("Err") {
+            Some(err) => Some(TransactionError::deserialize(err).map_err(|e| {
+                malformed(format!("unrecognized transaction error {}: {}", err, e))
+            })?),
+            None => None,
+        };
+        let transactions = entry
+            .transactions
+            .iter()
+            .map(|signature| {
+                signature
+                    .parse::<Signature>()
+                    .map_err(|e| malformed(format!("invalid signature {}: {}", signature, e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            confirmation_status: entry
+                .confirmation_status
+                .as_deref()
+                .and_then(CommitmentLevel::parse),
+            err,
+            transactions,
+            slot: entry.slot,
+            bundle_id: entry.bundle_id,
+        })
+    }
+}
+
+/// In-flight and final status of one bundle, from `get_combined_status`.
+#[derive(Debug, Clone)]
+pub struct CombinedStatus {
+    /// `Invalid` when the engine doesn't know the bundle.
+    pub inflight: InflightBundleStatus,
+    /// `None` until the bundle has a final status.
+    pub final_status: Option<BundleOutcome>,
+}
+
+/// Change in the state of a bundle tracked by `send_and_track`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleEvent {
+    Submitted(BundleId),
+    InFlight(InflightBundleStatus),
+    Committed(CommitmentLevel),
+}
+
+/// Polling settings for bundle confirmation.
+#[derive(Debug, Clone)]
+pub struct ConfirmOptions {
+    pub max_retries: u32,
+    pub retry_delay: Duration,
+    /// Commitment a landed bundle must reach before it counts as confirmed.
+    pub commitment: CommitmentLevel,
+    /// Derives the delay between polls from the observed RTT instead of
+    /// using `retry_delay`. Off by default.
+    pub adaptive_delay: Option<AdaptiveDelay>,
+    /// One deadline for a whole submit-and-confirm operation. Helpers that
+    /// submit as well as confirm, like `send_and_track` and
+    /// `submit_competitive`, apply it to submission and its retries too.
+    /// Whichever step is running when it passes fails with
+    /// `JitoError::DeadlineExceeded`. Unset by default.
+    pub operation_deadline: Option<Instant>,
+}
+
+impl Default for ConfirmOptions {
+    fn default() -> Self {
+        Self {
+            max_retries: 30,
+            retry_delay: Duration::from_secs(2),
+            commitment: CommitmentLevel::Finalized,
+            adaptive_delay: None,
+            operation_deadline: None,
+        }
+    }
+}
+
+impl ConfirmOptions {
+    // Falls back to `retry_delay` until an RTT has been observed.
+    pub(crate) fn delay(&self, rtt: Option<Duration>) -> Duration {
+        match (self.adaptive_delay, rtt) {
+            (Some(adaptive), Some(rtt)) => adaptive.delay(rtt),
+            _ => self.retry_delay,
+        }
+    }
+}
+
+/// Poll delay scaled from the SDK's rolling RTT estimate: `rtt * multiplier`,
+/// clamped to `min..=max`.
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveDelay {
+    pub multiplier: u32,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl Default for AdaptiveDelay {
+    fn default() -> Self {
+        Self {
+            multiplier: 4,
+            min: Duration::from_millis(100),
+            max: Duration::from_secs(2),
+        }
+    }
+}
+
+impl AdaptiveDelay {
+    pub fn delay(&self, rtt: Duration) -> Duration {
+        rtt.saturating_mul(self.multiplier)
+            .clamp(self.min, self.max)
+    }
+}
+
+/// One entry of a `getInflightBundleStatuses` response. Field names are
+/// accepted in snake_case or camelCase, since the engine's endpoints differ.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct InflightStatusEntry {
+    #[serde(alias = "bundleId")]
+    pub bundle_id: BundleId,
+    pub status: String,
+    #[serde(default, alias = "landedSlot", deserialize_with = "lenient_slot")]
+    pub landed_slot: Option<u64>,
+}
+
+/// One entry of a `getBundleStatuses` response, accepting snake_case or
+/// camelCase field names.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct BundleStatusEntry {
+    #[serde(alias = "bundleId")]
+    pub bundle_id: BundleId,
+    #[serde(default, deserialize_with = "lenient_slot")]
+    pub slot: Option<u64>,
+    #[serde(default, alias = "landedSlot", deserialize_with = "lenient_slot")]
+    pub landed_slot: Option<u64>,
+    #[serde(default, alias = "confirmationStatus")]
+    pub confirmation_status: Option<String>,
+    #[serde(default)]
+    pub transactions: Vec<String>,
+    #[serde(default)]
+    pub err: Value,
+}
+
+impl BundleStatusEntry {
+    // The engine reports success as `{"Ok": null}`, failure as
+    // `{"Err": ...}`.
+    pub fn has_error(&self) -> bool {
+        self.err.get("Err").is_some()
+    }
+}
+
+// Slots are numbers, but an unexpected shape (a string, a negative value)
+// reads as missing rather than failing the whole entry.
+fn lenient_slot<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::Number(slot) => slot.as_u64(),
+        Value::String(slot) => slot.parse().ok(),
+        _ => None,
+    })
+}
+
+// Entries of a status call's `value`, skipping the nulls the engine returns
+// for unknown bundle IDs and logging entries that don't parse.
+pub(crate) fn status_entries<T: DeserializeOwned>(result: &Value) -> Vec<T> {
+    result["value"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|entry| !entry.is_null())
+        .filter_map(|entry| match T::deserialize(entry) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                warn!("Skipping unparseable bundle status entry {}: {}", entry, e);
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(value: Value) -> BundleStatusEntry {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn status_entry_accepts_snake_case() {
+        let entry = entry(json!({
+            "bundle_id": "abc",
+            "slot": 42,
+            "confirmation_status": "confirmed",
+            "transactions": ["sig"],
+            "err": {"Ok": null},
+        }));
+        assert_eq!(entry.bundle_id, BundleId::from("abc"));
+        assert_eq!(entry.slot, Some(42));
+        assert_eq!(entry.confirmation_status.as_deref(), Some("confirmed"));
+        assert!(!entry.has_error());
+    }
+
+    #[test]
+    fn status_entry_accepts_camel_case() {
+        let entry = entry(json!({
+            "bundleId": "abc",
+            "slot": 42,
+            "confirmationStatus": "finalized",
+            "transactions": [],
+            "err": {"Ok": null},
+        }));
+        assert_eq!(entry.bundle_id, BundleId::from("abc"));
+        assert_eq!(entry.confirmation_status.as_deref(), Some("finalized"));
+        assert!(!entry.has_error());
+    }
+
+    #[test]
+    fn status_entry_with_err_has_error() {
+        let entry = entry(json!({
+            "bundle_id": "abc",
+            "slot": 42,
+            "confirmation_status": "processed",
+            "transactions": [],
+            "err": {"Err": {"InstructionError": [0, "Custom"]}},
+        }));
+        assert!(entry.has_error());
+    }
+
+    #[test]
+    fn status_entry_without_err_has_no_error() {
+        let entry = entry(json!({"bundle_id": "abc"}));
+        assert!(!entry.has_error());
+    }
+
+    // Parsed from text rather than `json!` so serde_json's own number
+    // parsing is exercised; anything routed through f64 would round.
+    #[test]
+    fn slots_near_u64_max_stay_exact() {
+        let entry: BundleStatusEntry = serde_json::from_str(
+            r#"{"bundle_id": "abc", "slot": 18446744073709551615, "landed_slot": "18446744073709551614"}"#,
+        )
+        .unwrap();
+        assert_eq!(entry.slot, Some(u64::MAX));
+        assert_eq!(entry.landed_slot, Some(u64::MAX - 1));
+    }
+}
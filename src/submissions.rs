@@ -0,0 +1,40 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::bundle::BundleId;
+
+/// Ring buffer of the most recently submitted bundle IDs and when they were
+/// submitted, shared across clones. Holds at most `capacity` entries; once
+/// full, the oldest is evicted first.
+#[derive(Debug, Clone)]
+pub struct RecentSubmissions {
+    capacity: usize,
+    inner: Arc<Mutex<VecDeque<(BundleId, Instant)>>>,
+}
+
+impl RecentSubmissions {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+        }
+    }
+
+    pub fn record(&self, bundle_id: BundleId) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+        if inner.len() >= self.capacity {
+            inner.pop_front();
+        }
+        inner.push_back((bundle_id, Instant::now()));
+    }
+
+    /// Recorded submissions, oldest first.
+    pub fn snapshot(&self) -> Vec<(BundleId, Instant)> {
+        self.inner.lock().unwrap().iter().cloned().collect()
+    }
+}
@@ -0,0 +1,591 @@
+use serde::Deserialize;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+use solana_system_interface::{instruction as system_instruction, program as system_program};
+use solana_transaction::{versioned::VersionedTransaction, Transaction};
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use crate::region::Network;
+use crate::status::ConfirmOptions;
+
+/// Tip accounts returned by `getTipAccounts`, stamped with when they were
+/// fetched.
+#[derive(Debug, Clone)]
+pub struct TipAccounts {
+    pub accounts: Vec<Pubkey>,
+    /// When the response was received. The engine rotates its tip accounts
+    /// rarely, but a long-lived copy can still go stale; callers that cache
+    /// the set can use `age` to decide when to refetch.
+    pub fetched_at: Instant,
+}
+
+impl TipAccounts {
+    pub fn age(&self) -> Duration {
+        self.fetched_at.elapsed()
+    }
+
+    /// Static tip accounts for `network`, for when `getTipAccounts` can't be
+    /// reached.
+    pub fn known_for(network: Network) -> &'static [Pubkey] {
+        match network {
+            Network::Mainnet => &MAINNET_TIP_ACCOUNTS,
+            Network::Testnet => &TESTNET_TIP_ACCOUNTS,
+        }
+    }
+}
+
+/// Number of tip accounts the block engine publishes.
+pub const TIP_ACCOUNT_COUNT: usize = 8;
+
+// The two sets below are copied from Jito's documentation and must be
+// updated by hand if the engine ever rotates its tip accounts; compare them
+// with a `getTipAccounts` response against each network when in doubt.
+
+/// Mainnet tip accounts.
+pub const MAINNET_TIP_ACCOUNTS: [Pubkey; TIP_ACCOUNT_COUNT] = [
+    Pubkey::from_str_const("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"),
+    Pubkey::from_str_const("HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe"),
+    Pubkey::from_str_const("Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY"),
+    Pubkey::from_str_const("ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49"),
+    Pubkey::from_str_const("DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh"),
+    Pubkey::from_str_const("ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt"),
+    Pubkey::from_str_const("DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL"),
+    Pubkey::from_str_const("3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT"),
+];
+
+/// Testnet tip accounts.
+pub const TESTNET_TIP_ACCOUNTS: [Pubkey; TIP_ACCOUNT_COUNT] = [
+    Pubkey::from_str_const("B1mrQSpdeMU9gCvkJ6VsXVVoYjRGkNA7TtjMyqxrhecH"),
+    Pubkey::from_str_const("aTtUk2DHgLhKZRDjePq6eiHRKC1XXFMBiSUfQ2JNDbN"),
+    Pubkey::from_str_const("E2eSqe33tuhAHKTrwky5uEjaVqnb2T9ns6nHHUrN8588"),
+    Pubkey::from_str_const("4xgEmT58RwTNsF5xcBTXwjcLC1rzSD2eeWpJvkqMhNjz"),
+    Pubkey::from_str_const("EoW3SUQap7ZeynXQ2QJ847aerhxbPVr843uMeTfc9dxM"),
+    Pubkey::from_str_const("ARTtviJkLLt6cHGQDydfo1Wyk6M4VGZdKZ2ZhdnJL336"),
+    Pubkey::from_str_const("9n3d1K5YD2vECAbRFhFFGYNNjiXtHXJWn9F31t89vsAV"),
+    Pubkey::from_str_const("9ttgPBBhRYFuQccdR1DSnb7hydsWANoDsV3P9kaGMCEh"),
+];
+
+/// Public endpoint serving recent landed-tip percentiles.
+pub const TIP_FLOOR_URL: &str = "https://bundles.jito.wtf/api/v1/bundles/tip_floor";
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// Landed-tip percentile reported by the tip floor endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TipPercentile {
+    P25,
+    P50,
+    P75,
+    P95,
+    P99,
+    /// Exponential moving average of the 50th percentile.
+    Ema50,
+}
+
+/// Tip used when a helper is called without an explicit amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefaultTip {
+    Lamports(u64),
+    /// Priced from the tip floor at the time of each call.
+    Percentile(TipPercentile),
+}
+
+/// Recent landed-tip statistics. Amounts are in SOL, as served by the
+/// endpoint; use `lamports` to get an exact tip amount.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TipFloor {
+    pub time: String,
+    pub landed_tips_25th_percentile: f64,
+    pub landed_tips_50th_percentile: f64,
+    pub landed_tips_75th_percentile: f64,
+    pub landed_tips_95th_percentile: f64,
+    pub landed_tips_99th_percentile: f64,
+    pub ema_landed_tips_50th_percentile: f64,
+}
+
+impl TipFloor {
+    pub fn sol(&self, percentile: TipPercentile) -> f64 {
+        match percentile {
+            TipPercentile::P25 => self.landed_tips_25th_percentile,
+            TipPercentile::P50 => self.landed_tips_50th_percentile,
+            TipPercentile::P75 => self.landed_tips_75th_percentile,
+            TipPercentile::P95 => self.landed_tips_95th_percentile,
+            TipPercentile::P99 => self.landed_tips_99th_percentile,
+            TipPercentile::Ema50 => self.ema_landed_tips_50th_percentile,
+        }
+    }
+
+    /// Tip at `percentile` in lamports. The SOL floats carry binary rounding
+    /// noise (1e-05 * 1e9 = 10000.000000000002), so round to the nearest
+    /// lamport rather than truncating or rounding up.
+    pub fn lamports(&self, percentile: TipPercentile) -> u64 {
+        (self.sol(percentile) * LAMPORTS_PER_SOL).round() as u64
+    }
+}
+
+/// Compute Budget program.
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("ComputeBudget111111111111111111111111111111");
+
+/// SPL Memo program (v2).
+pub const MEMO_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Transfer of `lamports` from `payer` to a Jito tip account.
+pub fn tip_ix(payer: &Pubkey, tip_account: &Pubkey, lamports: u64) -> Instruction {
+    system_instruction::transfer(payer, tip_account, lamports)
+}
+
+/// Memo instruction signed by `signer`, e.g. to tag a bundle transaction
+/// alongside its `tip_ix`.
+pub fn memo_ix(signer: &Pubkey, memo: &str) -> Instruction {
+    Instruction::new_with_bytes(
+        MEMO_PROGRAM_ID,
+        memo.as_bytes(),
+        vec![AccountMeta::new(*signer, true)],
+    )
+}
+
+/// `SetComputeUnitLimit` instruction of the Compute Budget program.
+pub fn compute_unit_limit_ix(units: u32) -> Instruction {
+    let mut data = vec![2];
+    data.extend_from_slice(&units.to_le_bytes());
+    Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, vec![])
+}
+
+/// `SetComputeUnitPrice` instruction of the Compute Budget program, as a
+/// priority fee in micro-lamports per compute unit.
+pub fn compute_unit_price_ix(micro_lamports: u64) -> Instruction {
+    let mut data = vec![3];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+    Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, vec![])
+}
+
+/// How `get_random_tip_account` and `get_random_tip_account_within` pick
+/// among the tip accounts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TipAccountStrategy {
+    /// Uniformly at random.
+    #[default]
+    Random,
+    /// Weighted towards accounts that landed more often through the
+    /// connected region, from outcomes reported with `record_tip_outcome`.
+    /// Uniformly at random while the region has no recorded outcomes.
+    RegionOptimized,
+}
+
+/// Compute budget a transaction requests, as set by `compute_unit_limit_ix`
+/// and `compute_unit_price_ix`. A field is `None` when the transaction has no
+/// such instruction, in which case the runtime's default applies.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ComputeBudgetInfo {
+    pub unit_limit: Option<u32>,
+    /// Priority fee in micro-lamports per compute unit.
+    pub unit_price_micro_lamports: Option<u64>,
+}
+
+/// Reads the Compute Budget instructions of `transaction` back into typed
+/// fields, e.g. to check the priority fee a built transaction will pay. The
+/// runtime rejects a transaction that repeats one of these instructions, so
+/// only the first of each kind is considered. Malformed instructions are
+/// ignored.
+pub fn extract_compute_budget(transaction: &Transaction) -> ComputeBudgetInfo {
+    let keys = &transaction.message.account_keys;
+    let mut info = ComputeBudgetInfo::default();
+
+    let budget_instructions =
+        transaction.message.instructions.iter().filter(|ix| {
+            keys.get(ix.program_id_index as usize) == Some(&COMPUTE_BUDGET_PROGRAM_ID)
+        });
+    for ix in budget_instructions {
+        match ix.data.split_first() {
+            Some((2, units)) if info.unit_limit.is_none() => {
+                info.unit_limit = units.try_into().ok().map(u32::from_le_bytes);
+            }
+            Some((3, price)) if info.unit_price_micro_lamports.is_none() => {
+                info.unit_price_micro_lamports = price.try_into().ok().map(u64::from_le_bytes);
+            }
+            _ => {}
+        }
+    }
+    info
+}
+
+/// Removes repeated Compute Budget instructions from `transaction`, keeping
+/// the last of each kind (unit limit, unit price, ...), and returns whether
+/// any were removed. The runtime rejects a transaction that repeats one of
+/// these instructions. Removing instructions invalidates existing
+/// signatures, so dedupe before signing.
+pub fn dedupe_compute_budget(transaction: &mut Transaction) -> bool {
+    let message = &mut transaction.message;
+    let keys = &message.account_keys;
+    let mut seen = HashSet::new();
+
+    let mut keep: Vec<bool> = message
+        .instructions
+        .iter()
+        .rev()
+        .map(|ix| {
+            keys.get(ix.program_id_index as usize) != Some(&COMPUTE_BUDGET_PROGRAM_ID)
+                || seen.insert(ix.data.first().copied())
+        })
+        .collect();
+    keep.reverse();
+    if keep.iter().all(|&keep| keep) {
+        return false;
+    }
+
+    let mut keep = keep.into_iter();
+    message.instructions.retain(|_| keep.next().unwrap_or(true));
+    true
+}
+
+/// Rebuilds `transaction` with `instruction` appended, keeping its fee payer
+/// (`default_payer` if it has no accounts yet) and recent blockhash.
+/// Signatures are reset. `None` if an instruction references an account key
+/// the message doesn't have.
+pub(crate) fn append_instruction(
+    transaction: &mut Transaction,
+    instruction: Instruction,
+    default_payer: &Pubkey,
+) -> Option<()> {
+    let message = &transaction.message;
+    let keys = &message.account_keys;
+    let header = &message.header;
+    let signed = header.num_required_signatures as usize;
+    let is_writable = |i: usize| {
+        if i < signed {
+            i < signed.saturating_sub(header.num_readonly_signed_accounts as usize)
+        } else {
+            i < keys
+                .len()
+                .saturating_sub(header.num_readonly_unsigned_accounts as usize)
+        }
+    };
+
+    let mut instructions = message
+        .instructions
+        .iter()
+        .map(|ix| {
+            let accounts = ix
+                .accounts
+                .iter()
+                .map(|&i| {
+                    let i = i as usize;
+                    Some(AccountMeta {
+                        pubkey: *keys.get(i)?,
+                        is_signer: i < signed,
+                        is_writable: is_writable(i),
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(Instruction {
+                program_id: *keys.get(ix.program_id_index as usize)?,
+                accounts,
+                data: ix.data.clone(),
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    instructions.push(instruction);
+
+    let payer = keys.first().copied().unwrap_or(*default_payer);
+    let recent_blockhash = message.recent_blockhash;
+    *transaction = Transaction::new_with_payer(&instructions, Some(&payer));
+    transaction.message.recent_blockhash = recent_blockhash;
+    Some(())
+}
+
+/// Settings for `submit_competitive`.
+#[derive(Debug, Clone)]
+pub struct CompetitiveConfig {
+    /// Tip floor percentile the tip is priced at.
+    pub tip_percentile: TipPercentile,
+    /// Tip used when the tip floor can't be fetched.
+    pub fallback_tip_lamports: u64,
+    /// Upper bound on the tip, whatever the floor says.
+    pub max_tip_lamports: Option<u64>,
+    /// Compute unit limit set on every transaction. Left unset by default.
+    pub compute_unit_limit: Option<u32>,
+    /// Priority fee set on every transaction. Left unset by default.
+    pub compute_unit_price_micro_lamports: Option<u64>,
+    pub confirm: ConfirmOptions,
+}
+
+impl Default for CompetitiveConfig {
+    fn default() -> Self {
+        Self {
+            tip_percentile: TipPercentile::P75,
+            fallback_tip_lamports: 10_000,
+            max_tip_lamports: None,
+            compute_unit_limit: None,
+            compute_unit_price_micro_lamports: None,
+            confirm: ConfirmOptions::default(),
+        }
+    }
+}
+
+impl CompetitiveConfig {
+    /// `tip_lamports` limited to `max_tip_lamports`.
+    pub fn capped_tip(&self, tip_lamports: u64) -> u64 {
+        self.max_tip_lamports
+            .map_or(tip_lamports, |max| tip_lamports.min(max))
+    }
+
+    /// Compute budget instructions for the configured limit and price.
+    pub fn compute_budget_ixs(&self) -> Vec<Instruction> {
+        self.compute_unit_limit
+            .map(compute_unit_limit_ix)
+            .into_iter()
+            .chain(
+                self.compute_unit_price_micro_lamports
+                    .map(compute_unit_price_ix),
+            )
+            .collect()
+    }
+}
+
+/// How `resubmit_with_escalated_tip` raises the tip of a bundle that didn't
+/// land.
+#[derive(Debug, Clone)]
+pub struct TipEscalation {
+    /// Upper bound on the escalated tip. A tip already above it is kept.
+    pub max_tip_lamports: u64,
+    /// Raise, in percent of the current tip, used once the tip is at or
+    /// above every tip floor percentile or the floor can't be fetched.
+    pub fallback_increase_percent: u64,
+}
+
+impl TipEscalation {
+    pub fn new(max_tip_lamports: u64) -> Self {
+        Self {
+            max_tip_lamports,
+            fallback_increase_percent: 50,
+        }
+    }
+
+    /// Tip to retry with after `current`: the lowest tip floor percentile
+    /// above it, otherwise `current` raised by `fallback_increase_percent`,
+    /// capped at `max_tip_lamports`.
+    pub fn next_tip(&self, current: u64, floor: Option<&TipFloor>) -> u64 {
+        const STEPS: [TipPercentile; 5] = [
+            TipPercentile::P25,
+            TipPercentile::P50,
+            TipPercentile::P75,
+            TipPercentile::P95,
+            TipPercentile::P99,
+        ];
+
+        let next = floor
+            .and_then(|floor| {
+                STEPS
+                    .iter()
+                    .map(|&percentile| floor.lamports(percentile))
+                    .find(|&tip| tip > current)
+            })
+            .unwrap_or_else(|| {
+                let raise = current.saturating_mul(self.fallback_increase_percent) / 100;
+                current.saturating_add(raise.max(1))
+            });
+        next.min(self.max_tip_lamports).max(current)
+    }
+}
+
+/// What `send_txn` does with a bundle-only transaction that pays no tip to
+/// a tip account. Without a tip the engine won't prioritize it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TipCheck {
+    Off,
+    #[default]
+    Warn,
+    Reject,
+}
+
+// System transfer instruction data: the `Transfer` variant index followed by
+// the amount, both little-endian.
+const TRANSFER_TAG: [u8; 4] = 2u32.to_le_bytes();
+const TRANSFER_DATA_LEN: usize = 12;
+
+// Amount of a compiled instruction if it is a system transfer to one of
+// `tip_accounts`.
+fn tip_transfer_lamports(
+    keys: &[Pubkey],
+    program_id_index: u8,
+    accounts: &[u8],
+    data: &[u8],
+    tip_accounts: &[Pubkey],
+) -> Option<u64> {
+    let is_transfer = keys.get(program_id_index as usize) == Some(&system_program::ID)
+        && data.len() == TRANSFER_DATA_LEN
+        && data[..4] == TRANSFER_TAG;
+    let recipient = accounts.get(1).and_then(|&i| keys.get(i as usize))?;
+    if !is_transfer || !tip_accounts.contains(recipient) {
+        return None;
+    }
+    Some(u64::from_le_bytes(data[4..].try_into().ok()?))
+}
+
+/// Whether `transaction` transfers lamports to one of `tip_accounts`. Only
+/// static account keys are checked, so a tip account loaded from a lookup
+/// table isn't recognized.
+pub(crate) fn pays_tip(transaction: &VersionedTransaction, tip_accounts: &[Pubkey]) -> bool {
+    let keys = transaction.message.static_account_keys();
+    transaction.message.instructions().iter().any(|ix| {
+        tip_transfer_lamports(
+            keys,
+            ix.program_id_index,
+            &ix.accounts,
+            &ix.data,
+            tip_accounts,
+        )
+        .is_some()
+    })
+}
+
+/// Lamports `transaction` transfers to `tip_accounts`, over all its tip
+/// transfers.
+pub(crate) fn tip_lamports(transaction: &Transaction, tip_accounts: &[Pubkey]) -> u64 {
+    let keys = &transaction.message.account_keys;
+    transaction
+        .message
+        .instructions
+        .iter()
+        .filter_map(|ix| {
+            tip_transfer_lamports(
+                keys,
+                ix.program_id_index,
+                &ix.accounts,
+                &ix.data,
+                tip_accounts,
+            )
+        })
+        .fold(0, u64::saturating_add)
+}
+
+/// `tip_lamports` for a versioned transaction. As in `pays_tip`, only
+/// static account keys are checked.
+pub(crate) fn versioned_tip_lamports(
+    transaction: &VersionedTransaction,
+    tip_accounts: &[Pubkey],
+) -> u64 {
+    let keys = transaction.message.static_account_keys();
+    transaction
+        .message
+        .instructions()
+        .iter()
+        .filter_map(|ix| {
+            tip_transfer_lamports(
+                keys,
+                ix.program_id_index,
+                &ix.accounts,
+                &ix.data,
+                tip_accounts,
+            )
+        })
+        .fold(0, u64::saturating_add)
+}
+
+/// Index and amount of the last system transfer in `transaction` that pays
+/// one of `tip_accounts`.
+#[cfg(feature = "solana-rpc")]
+pub(crate) fn find_tip_transfer(
+    transaction: &Transaction,
+    tip_accounts: &[Pubkey],
+) -> Option<(usize, u64)> {
+    let keys = &transaction.message.account_keys;
+    transaction
+        .message
+        .instructions
+        .iter()
+        .enumerate()
+        .rev()
+        .find_map(|(index, ix)| {
+            let lamports = tip_transfer_lamports(
+                keys,
+                ix.program_id_index,
+                &ix.accounts,
+                &ix.data,
+                tip_accounts,
+            )?;
+            Some((index, lamports))
+        })
+}
+
+/// Rewrites the amount of the transfer at `index`, as found by
+/// `find_tip_transfer`. Existing signatures become invalid.
+#[cfg(feature = "solana-rpc")]
+pub(crate) fn set_transfer_lamports(transaction: &mut Transaction, index: usize, lamports: u64) {
+    transaction.message.instructions[index].data[4..].copy_from_slice(&lamports.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memo_ix_targets_the_memo_program_with_the_memo_as_data() {
+        let signer = Pubkey::new_unique();
+        let ix = memo_ix(&signer, "jito bundle #1");
+
+        assert_eq!(
+            ix.program_id.to_string(),
+            "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"
+        );
+        assert_eq!(ix.data, b"jito bundle #1");
+        assert_eq!(ix.accounts, vec![AccountMeta::new(signer, true)]);
+    }
+
+    #[test]
+    fn dedupe_keeps_the_last_of_repeated_unit_prices() {
+        let payer = Pubkey::new_unique();
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                compute_unit_price_ix(1_000),
+                compute_unit_limit_ix(200_000),
+                compute_unit_price_ix(5_000),
+                memo_ix(&payer, "swap"),
+            ],
+            Some(&payer),
+        );
+
+        assert!(dedupe_compute_budget(&mut transaction));
+        assert_eq!(transaction.message.instructions.len(), 3);
+        assert_eq!(
+            extract_compute_budget(&transaction),
+            ComputeBudgetInfo {
+                unit_limit: Some(200_000),
+                unit_price_micro_lamports: Some(5_000),
+            }
+        );
+        assert!(!dedupe_compute_budget(&mut transaction));
+    }
+
+    #[test]
+    fn mainnet_and_testnet_share_no_tip_account() {
+        let mainnet = TipAccounts::known_for(Network::Mainnet);
+        let testnet = TipAccounts::known_for(Network::Testnet);
+
+        assert_eq!(mainnet, MAINNET_TIP_ACCOUNTS);
+        assert_eq!(testnet, TESTNET_TIP_ACCOUNTS);
+        assert!(mainnet.iter().all(|account| !testnet.contains(account)));
+    }
+
+    // Response captured from bundles.jito.wtf/api/v1/bundles/tip_floor.
+    const TIP_FLOOR_SAMPLE: &str = r#"[{"time":"2024-09-01T12:58:00Z","landed_tips_25th_percentile":6.001000000000001e-06,"landed_tips_50th_percentile":1e-05,"landed_tips_75th_percentile":3.6196500000000005e-05,"landed_tips_95th_percentile":0.0014479055000000002,"landed_tips_99th_percentile":0.010007999,"ema_landed_tips_50th_percentile":9.836078125000002e-06}]"#;
+
+    #[test]
+    fn captured_tip_floor_deserializes_to_lamports() {
+        let floors: Vec<TipFloor> = serde_json::from_str(TIP_FLOOR_SAMPLE).unwrap();
+        assert_eq!(floors.len(), 1);
+        let floor = &floors[0];
+
+        assert_eq!(floor.time, "2024-09-01T12:58:00Z");
+        assert_eq!(floor.sol(TipPercentile::P50), 1e-05);
+        assert_eq!(floor.lamports(TipPercentile::P25), 6_001);
+        assert_eq!(floor.lamports(TipPercentile::P50), 10_000);
+        assert_eq!(floor.lamports(TipPercentile::P75), 36_197);
+        assert_eq!(floor.lamports(TipPercentile::P95), 1_447_906);
+        assert_eq!(floor.lamports(TipPercentile::P99), 10_007_999);
+        assert_eq!(floor.lamports(TipPercentile::Ema50), 9_836);
+    }
+}
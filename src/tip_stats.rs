@@ -0,0 +1,133 @@
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+use serde::Serialize;
+use solana_pubkey::Pubkey;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Observed outcomes of bundles tipping one account through one block engine
+/// region (base URL), as recorded with `record_tip_outcome`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TipAccountStats {
+    pub region: String,
+    pub account: Pubkey,
+    pub submitted: u64,
+    pub landed: u64,
+}
+
+impl TipAccountStats {
+    pub fn land_rate(&self) -> f64 {
+        if self.submitted == 0 {
+            return 0.0;
+        }
+        self.landed as f64 / self.submitted as f64
+    }
+}
+
+// `(submitted, landed)` per `(region, account)`.
+type LandCounts = HashMap<(String, Pubkey), (u64, u64)>;
+
+/// Per-region, per-account land counts, shared across clones.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TipLandStats {
+    inner: Arc<Mutex<LandCounts>>,
+}
+
+impl TipLandStats {
+    pub fn record(&self, region: &str, account: Pubkey, landed: bool) {
+        let mut inner = self.inner.lock().unwrap();
+        let (submitted, landed_count) = inner.entry((region.to_string(), account)).or_default();
+        *submitted += 1;
+        *landed_count += u64::from(landed);
+    }
+
+    pub fn snapshot(&self) -> Vec<TipAccountStats> {
+        self.inner
+            .lock()
+            .unwrap()
+            .iter()
+            .map(
+                |((region, account), &(submitted, landed))| TipAccountStats {
+                    region: region.clone(),
+                    account: *account,
+                    submitted,
+                    landed,
+                },
+            )
+            .collect()
+    }
+
+    /// Index into `accounts` drawn with probability proportional to each
+    /// account's smoothed land rate in `region`, `(landed + 1) /
+    /// (submitted + 2)`, so accounts without data still get picked. `None`
+    /// when no account has data in `region`.
+    pub fn choose<R: Rng>(&self, region: &str, accounts: &[Pubkey], rng: &mut R) -> Option<usize> {
+        let inner = self.inner.lock().unwrap();
+        let counts: Vec<Option<(u64, u64)>> = accounts
+            .iter()
+            .map(|account| inner.get(&(region.to_string(), *account)).copied())
+            .collect();
+        drop(inner);
+        if counts.iter().all(Option::is_none) {
+            return None;
+        }
+
+        let weights = counts.iter().map(|counts| {
+            let (submitted, landed) = counts.unwrap_or_default();
+            (landed as f64 + 1.0) / (submitted as f64 + 2.0)
+        });
+        Some(WeightedIndex::new(weights).ok()?.sample(rng))
+    }
+}
+
+/// Lamports spent on tips, as returned by `TipSpend::totals`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TipSpendTotals {
+    /// Tips in bundles the engine accepted.
+    pub submitted_lamports: u64,
+    /// Tips in bundles that landed.
+    pub landed_lamports: u64,
+    /// Tips in bundles that failed or were dropped.
+    pub failed_lamports: u64,
+}
+
+/// Running tip spend totals. Clones share the same counters, so a handle
+/// from `JitoJsonRpcSDK::tip_spend` keeps reflecting the SDK's submissions.
+#[derive(Debug, Clone, Default)]
+pub struct TipSpend {
+    submitted: Arc<AtomicU64>,
+    landed: Arc<AtomicU64>,
+    failed: Arc<AtomicU64>,
+}
+
+impl TipSpend {
+    pub fn totals(&self) -> TipSpendTotals {
+        TipSpendTotals {
+            submitted_lamports: self.submitted.load(Ordering::Relaxed),
+            landed_lamports: self.landed.load(Ordering::Relaxed),
+            failed_lamports: self.failed.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Zeroes the counters and returns what they held, e.g. to close an
+    /// accounting period. Each counter is swapped on its own, so a
+    /// submission racing the reset may land in either period.
+    pub fn reset(&self) -> TipSpendTotals {
+        TipSpendTotals {
+            submitted_lamports: self.submitted.swap(0, Ordering::Relaxed),
+            landed_lamports: self.landed.swap(0, Ordering::Relaxed),
+            failed_lamports: self.failed.swap(0, Ordering::Relaxed),
+        }
+    }
+
+    /// Adds the tip of a bundle whose outcome is known. The SDK does this
+    /// itself for bundles it both submits and confirms.
+    pub fn record_outcome(&self, lamports: u64, landed: bool) {
+        let counter = if landed { &self.landed } else { &self.failed };
+        counter.fetch_add(lamports, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_submitted(&self, lamports: u64) {
+        self.submitted.fetch_add(lamports, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "cert-pinning")]
+
+use std::sync::Arc;
+
+use jito_sdk_rust::{ConfigError, JitoError, JitoJsonRpcSDK};
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use tokio_rustls::rustls::{Certificate, PrivateKey, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+
+const CERT_PEM: &[u8] = include_bytes!("fixtures/localhost.crt");
+const KEY_PEM: &[u8] = include_bytes!("fixtures/localhost.key");
+// `openssl x509 -in tests/fixtures/localhost.crt -outform DER | sha256sum`
+const CERT_SHA256: &str = "f198a3c57f340529b127a6fbad3b83540b36d65754580f36d15726d3c0005dff";
+const OTHER_SHA256: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+// TLS server presenting the fixture certificate for one connection. Reports
+// how many bytes of request data it received after the handshake, if the
+// handshake completed at all.
+async fn tls_server() -> (String, oneshot::Receiver<Option<usize>>) {
+    let certs = rustls_pemfile::certs(&mut &CERT_PEM[..]).unwrap();
+    let key = rustls_pemfile::pkcs8_private_keys(&mut &KEY_PEM[..])
+        .unwrap()
+        .remove(0);
+    let config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(
+            certs.into_iter().map(Certificate).collect(),
+            PrivateKey(key),
+        )
+        .unwrap();
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("https://{}/api/v1", listener.local_addr().unwrap());
+    let (received_tx, received_rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let received = match acceptor.accept(stream).await {
+            Ok(mut tls) => {
+                let mut buf = vec![0u8; 8192];
+                Some(tls.read(&mut buf).await.unwrap_or(0))
+            }
+            Err(_) => None,
+        };
+        let _ = received_tx.send(received);
+    });
+    (url, received_rx)
+}
+
+#[tokio::test]
+async fn mismatched_certificate_fails_the_handshake() {
+    let (url, received) = tls_server().await;
+    let sdk = JitoJsonRpcSDK::builder(&url)
+        .uuid("00000000-0000-0000-0000-000000000000")
+        .pin_cert_sha256(OTHER_SHA256)
+        .build()
+        .unwrap();
+
+    match sdk.get_tip_accounts().await {
+        Err(JitoError::CertificatePinMismatch { expected, actual }) => {
+            assert_eq!(expected, OTHER_SHA256);
+            assert_eq!(actual.as_deref(), Some(CERT_SHA256));
+        }
+        other => panic!("expected CertificatePinMismatch, got {:?}", other),
+    }
+    // The client aborted the handshake, so no request, UUID or auth header
+    // reached the server.
+    assert_eq!(received.await.unwrap(), None);
+}
+
+#[test]
+fn plain_http_endpoints_cannot_be_pinned() {
+    let builder =
+        JitoJsonRpcSDK::builder("http://127.0.0.1:8080/api/v1").pin_cert_sha256(CERT_SHA256);
+
+    assert_eq!(
+        builder.validate(),
+        Err(vec![ConfigError::PinnedPlainHttp(
+            "http://127.0.0.1:8080/api/v1".to_string()
+        )])
+    );
+    assert!(builder.build().is_err());
+}
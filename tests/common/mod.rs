@@ -0,0 +1,111 @@
+//! Minimal HTTP/1.1 server standing in for the block engine. Each reply is
+//! served on its own connection, in the order connections arrive, and the
+//! request bodies are kept for inspection.
+#![allow(dead_code)]
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+pub struct Reply {
+    bytes: Vec<u8>,
+    delay: Duration,
+}
+
+impl Reply {
+    pub fn new(status: &str, headers: &[(&str, &str)], body: &[u8]) -> Self {
+        let mut head = format!("HTTP/1.1 {}\r\nConnection: close\r\n", status);
+        for (name, value) in headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str(&format!("Content-Length: {}\r\n\r\n", body.len()));
+
+        let mut bytes = head.into_bytes();
+        bytes.extend_from_slice(body);
+        Self {
+            bytes,
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn json(status: &str, body: &str) -> Self {
+        Self::new(
+            status,
+            &[("Content-Type", "application/json")],
+            body.as_bytes(),
+        )
+    }
+
+    /// Waits `delay` after reading the request before replying.
+    pub fn after(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+pub struct MockServer {
+    /// Base URL to hand to the SDK, e.g. `http://127.0.0.1:1234/api/v1`.
+    pub url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockServer {
+    pub async fn start(replies: Vec<Reply>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/api/v1", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let received = requests.clone();
+        tokio::spawn(async move {
+            for reply in replies {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let body = read_request(&mut stream).await;
+                received.lock().unwrap().push(body);
+                // A delayed reply must not hold up the next connection.
+                tokio::spawn(async move {
+                    tokio::time::sleep(reply.delay).await;
+                    // The client may have given up already.
+                    let _ = stream.write_all(&reply.bytes).await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// Bodies of the requests received so far.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> String {
+    let mut received = Vec::new();
+    let mut buf = [0u8; 8192];
+    loop {
+        let n = stream.read(&mut buf).await.unwrap_or(0);
+        if n == 0 {
+            return String::new();
+        }
+        received.extend_from_slice(&buf[..n]);
+
+        let text = String::from_utf8_lossy(&received);
+        let Some(header_end) = text.find("\r\n\r\n") else {
+            continue;
+        };
+        let content_length = text[..header_end]
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+        let body_start = header_end + 4;
+        if received.len() >= body_start + content_length {
+            return String::from_utf8_lossy(&received[body_start..body_start + content_length])
+                .into_owned();
+        }
+    }
+}
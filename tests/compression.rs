@@ -0,0 +1,35 @@
+#![cfg(feature = "compression")]
+
+mod common;
+
+use std::io::Write;
+
+use common::{MockServer, Reply};
+use flate2::{write::GzEncoder, Compression};
+use jito_sdk_rust::JitoJsonRpcSDK;
+use serde_json::json;
+
+#[tokio::test]
+async fn gzipped_response_is_decompressed() {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": ["96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"],
+    });
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(body.to_string().as_bytes()).unwrap();
+    let gzipped = encoder.finish().unwrap();
+
+    let server = MockServer::start(vec![Reply::new(
+        "200 OK",
+        &[
+            ("Content-Type", "application/json"),
+            ("Content-Encoding", "gzip"),
+        ],
+        &gzipped,
+    )])
+    .await;
+    let sdk = JitoJsonRpcSDK::new(&server.url, None);
+
+    assert_eq!(sdk.get_tip_accounts().await.unwrap(), body);
+}
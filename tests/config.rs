@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use jito_sdk_rust::{builder::ConfigError, JitoJsonRpcSDK};
+
+const BASE_URL: &str = "https://mainnet.block-engine.jito.wtf/api/v1";
+
+fn timeout_errors(errors: Vec<ConfigError>) -> Vec<String> {
+    errors
+        .into_iter()
+        .filter_map(|error| match error {
+            ConfigError::InvalidTimeout(problem) => Some(problem),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn default_timeouts_are_valid() {
+    assert_eq!(JitoJsonRpcSDK::builder(BASE_URL).validate(), Ok(()));
+}
+
+#[test]
+fn zero_timeouts_are_rejected() {
+    let errors = JitoJsonRpcSDK::builder(BASE_URL)
+        .timeout(Duration::ZERO)
+        .method_timeout("getBundleStatuses", Duration::ZERO)
+        .connect_timeout(Duration::ZERO)
+        .validate()
+        .unwrap_err();
+
+    assert_eq!(
+        timeout_errors(errors),
+        [
+            "timeout must be greater than zero",
+            "method_timeout for getBundleStatuses must be greater than zero",
+            "connect_timeout must be greater than zero",
+        ]
+    );
+}
+
+#[test]
+fn connect_timeout_longer_than_timeout_is_rejected() {
+    let errors = JitoJsonRpcSDK::builder(BASE_URL)
+        .timeout(Duration::from_secs(5))
+        .connect_timeout(Duration::from_secs(10))
+        .validate()
+        .unwrap_err();
+
+    assert_eq!(
+        timeout_errors(errors),
+        ["connect_timeout 10s exceeds timeout 5s"]
+    );
+}
+
+#[test]
+fn connect_timeout_within_timeout_is_accepted() {
+    let result = JitoJsonRpcSDK::builder(BASE_URL)
+        .timeout(Duration::from_secs(5))
+        .connect_timeout(Duration::from_secs(5))
+        .validate();
+
+    assert_eq!(result, Ok(()));
+}
@@ -0,0 +1,53 @@
+use base64::{engine::general_purpose, Engine as _};
+use jito_sdk_rust::{encode_versioned_transactions, validate_encoded_transactions, Encoding};
+use solana_hash::Hash;
+use solana_keypair::Keypair;
+use solana_message::{v0, AddressLookupTableAccount, VersionedMessage};
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use solana_system_interface::instruction as system_instruction;
+use solana_transaction::versioned::VersionedTransaction;
+
+// A v0 transfer whose recipient is only reachable through a lookup table.
+fn v0_transaction_with_lookup_table() -> VersionedTransaction {
+    let payer = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let table = AddressLookupTableAccount {
+        key: Pubkey::new_unique(),
+        addresses: vec![Pubkey::new_unique(), recipient],
+    };
+    let message = v0::Message::try_compile(
+        &payer.pubkey(),
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            &recipient,
+            1_000,
+        )],
+        &[table],
+        Hash::new_unique(),
+    )
+    .unwrap();
+    VersionedTransaction::try_new(VersionedMessage::V0(message), &[&payer]).unwrap()
+}
+
+#[test]
+fn v0_transaction_with_lookup_table_round_trips() {
+    let transaction = v0_transaction_with_lookup_table();
+    let lookups = transaction.message.address_table_lookups().unwrap();
+    assert_eq!(lookups.len(), 1);
+    assert_eq!(lookups[0].writable_indexes, vec![1]);
+
+    for encoding in [Encoding::Base64, Encoding::Base58] {
+        let encoded =
+            encode_versioned_transactions(std::slice::from_ref(&transaction), encoding).unwrap();
+        validate_encoded_transactions(&encoded, encoding).unwrap();
+
+        let bytes = match encoding {
+            Encoding::Base64 => general_purpose::STANDARD.decode(&encoded[0]).unwrap(),
+            Encoding::Base58 => bs58::decode(&encoded[0]).into_vec().unwrap(),
+        };
+        let decoded: VersionedTransaction = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, transaction);
+        assert_eq!(decoded.message.address_table_lookups(), Some(lookups));
+    }
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::{MockServer, Reply};
+use jito_sdk_rust::{JitoError, JitoJsonRpcSDK};
+
+#[tokio::test]
+async fn html_error_page_is_an_unexpected_response() {
+    let page = format!(
+        "<html>\n<head><title>502 Bad Gateway</title></head>\n<body>{}</body>\n</html>\n",
+        "<p>The origin is unreachable.</p>".repeat(10)
+    );
+    let server = MockServer::start(vec![Reply::new(
+        "502 Bad Gateway",
+        &[("Content-Type", "text/html; charset=UTF-8")],
+        page.as_bytes(),
+    )])
+    .await;
+    let sdk = JitoJsonRpcSDK::new(&server.url, None);
+
+    match sdk.get_tip_accounts().await {
+        Err(JitoError::UnexpectedResponse {
+            content_type,
+            snippet,
+        }) => {
+            assert_eq!(content_type, "text/html; charset=UTF-8");
+            assert!(snippet.starts_with("<html>\n<head><title>502 Bad Gateway</title>"));
+            assert!(snippet.ends_with("..."));
+            assert_eq!(snippet.chars().count(), 200 + "...".len());
+        }
+        other => panic!("expected UnexpectedResponse, got {:?}", other),
+    }
+}
+
+fn rpc_error(data: serde_json::Value) -> Reply {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "error": {"code": -32602, "message": "bundle rejected", "data": data},
+    });
+    Reply::json("200 OK", &body.to_string())
+}
+
+#[tokio::test]
+async fn rpc_error_data_is_kept_and_parsed() {
+    let data = serde_json::json!({
+        "transactionIndex": 2,
+        "err": {"InstructionError": [0, {"Custom": 1}]},
+        "logs": ["Program log: insufficient funds"],
+    });
+    let server = MockServer::start(vec![rpc_error(data.clone())]).await;
+    let sdk = JitoJsonRpcSDK::new(&server.url, None);
+
+    let err = sdk
+        .send_bundle_typed(Some(serde_json::json!(["AQ=="])), None)
+        .await
+        .unwrap_err();
+
+    match &err {
+        JitoError::RpcError {
+            code,
+            data: Some(kept),
+            ..
+        } => {
+            assert_eq!(*code, -32602);
+            assert_eq!(kept, &data);
+        }
+        other => panic!("expected RpcError with data, got {:?}", other),
+    }
+    let parsed = err.rpc_error_data().unwrap();
+    assert_eq!(parsed.transaction_index, Some(2));
+    assert_eq!(parsed.err, Some(data["err"].clone()));
+    assert_eq!(parsed.logs, ["Program log: insufficient funds"]);
+}
+
+#[tokio::test]
+async fn unrecognized_rpc_error_data_is_kept_raw() {
+    let data = serde_json::json!({"retryAfterSlots": 4});
+    let server = MockServer::start(vec![rpc_error(data.clone())]).await;
+    let sdk = JitoJsonRpcSDK::new(&server.url, None);
+
+    let err = sdk
+        .send_bundle_typed(Some(serde_json::json!(["AQ=="])), None)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(&err, JitoError::RpcError { data: Some(kept), .. } if kept == &data));
+    assert_eq!(err.rpc_error_data(), None);
+}
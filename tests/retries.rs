@@ -0,0 +1,131 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{MockServer, Reply};
+use jito_sdk_rust::{JitoError, JitoJsonRpcSDK};
+use reqwest::StatusCode;
+use serde_json::json;
+
+const OK: &str = r#"{"jsonrpc": "2.0", "id": 1, "result": "ok"}"#;
+
+// Base URL of a port nothing listens on, so connecting to it fails.
+async fn closed_endpoint() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    format!("http://{}/api/v1", addr)
+}
+
+#[tokio::test]
+async fn connection_failures_are_retried_for_submissions() {
+    let server = MockServer::start(vec![Reply::json("200 OK", OK)]).await;
+    let sdk = JitoJsonRpcSDK::builder(&closed_endpoint().await)
+        .endpoints(vec![closed_endpoint().await, server.url.clone()])
+        .connection_retries(2)
+        .build()
+        .unwrap();
+
+    let (_, debug) = sdk
+        .call_verbose("sendBundle", Some(json!([["AQ=="]])))
+        .await
+        .unwrap();
+
+    // Three attempts at the closed endpoint, then one at the live one.
+    assert_eq!(debug.attempts, 4);
+    assert!(debug.url.starts_with(&server.url));
+}
+
+#[tokio::test]
+async fn timeouts_are_retried_for_reads() {
+    let server = MockServer::start(vec![
+        Reply::json("200 OK", OK).after(Duration::from_secs(2)),
+        Reply::json("200 OK", OK),
+    ])
+    .await;
+    let sdk = JitoJsonRpcSDK::builder(&server.url)
+        .timeout(Duration::from_millis(200))
+        .connection_retries(1)
+        .build()
+        .unwrap();
+
+    let (_, debug) = sdk.call_verbose("getTipAccounts", None).await.unwrap();
+
+    assert_eq!(debug.attempts, 2);
+}
+
+#[tokio::test]
+async fn timeouts_are_not_retried_for_submissions() {
+    let server = MockServer::start(vec![
+        Reply::json("200 OK", OK).after(Duration::from_secs(2)),
+        Reply::json("200 OK", OK),
+    ])
+    .await;
+    let sdk = JitoJsonRpcSDK::builder(&server.url)
+        .timeout(Duration::from_millis(200))
+        .connection_retries(1)
+        .build()
+        .unwrap();
+
+    let result = sdk
+        .call_verbose("sendBundle", Some(json!([["AQ=="]])))
+        .await;
+
+    assert!(matches!(result, Err(JitoError::Timeout { .. })));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn server_errors_fail_over_to_the_next_endpoint() {
+    let primary = MockServer::start(vec![Reply::json("503 Service Unavailable", "{}")]).await;
+    let fallback = MockServer::start(vec![Reply::json("200 OK", OK)]).await;
+    let sdk = JitoJsonRpcSDK::with_endpoints(vec![primary.url.clone(), fallback.url.clone()], None)
+        .unwrap();
+
+    let (_, debug) = sdk
+        .call_verbose("sendBundle", Some(json!([["AQ=="]])))
+        .await
+        .unwrap();
+
+    assert_eq!(debug.attempts, 2);
+    assert!(debug.url.starts_with(&fallback.url));
+    assert_eq!(debug.status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn rate_limited_requests_are_retried() {
+    let server = MockServer::start(vec![
+        Reply::new(
+            "429 Too Many Requests",
+            &[("Content-Type", "application/json"), ("Retry-After", "0")],
+            b"{}",
+        ),
+        Reply::json("200 OK", OK),
+    ])
+    .await;
+    let sdk = JitoJsonRpcSDK::new(&server.url, None);
+
+    let (_, debug) = sdk.call_verbose("getTipAccounts", None).await.unwrap();
+
+    assert_eq!(debug.attempts, 2);
+    assert_eq!(debug.status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn client_errors_are_not_retried() {
+    let server = MockServer::start(vec![
+        Reply::json("400 Bad Request", r#"{"error": "bad request"}"#),
+        Reply::json("200 OK", OK),
+    ])
+    .await;
+    let sdk = JitoJsonRpcSDK::builder(&server.url)
+        .connection_retries(2)
+        .build()
+        .unwrap();
+
+    let (_, debug) = sdk.call_verbose("getTipAccounts", None).await.unwrap();
+
+    assert_eq!(debug.attempts, 1);
+    assert_eq!(debug.status, StatusCode::BAD_REQUEST);
+    assert_eq!(server.requests().len(), 1);
+}
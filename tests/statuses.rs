@@ -0,0 +1,94 @@
+mod common;
+
+use common::{MockServer, Reply};
+use jito_sdk_rust::{BundleId, JitoError, JitoJsonRpcSDK};
+use serde_json::json;
+use solana_signature::Signature;
+
+fn statuses(value: serde_json::Value) -> Reply {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "result": {"context": {"slot": 100}, "value": value},
+    });
+    Reply::json("200 OK", &body.to_string())
+}
+
+fn landed(bundle_id: &str) -> serde_json::Value {
+    json!({
+        "bundle_id": bundle_id,
+        "slot": 99,
+        "confirmation_status": "confirmed",
+        "transactions": [Signature::from([1; 64]).to_string()],
+        "err": {"Ok": null},
+    })
+}
+
+fn ids(ids: &[&str]) -> Vec<BundleId> {
+    ids.iter().copied().map(BundleId::from).collect()
+}
+
+#[tokio::test]
+async fn batch_reports_each_bundle_separately() {
+    let server = MockServer::start(vec![statuses(json!([
+        landed("landed"),
+        {
+            "bundle_id": "failed",
+            "slot": 99,
+            "transactions": [],
+            "err": {"Err": {"InstructionError": [0, "Custom"]}},
+        },
+        {"bundle_id": "malformed", "slot": 99, "transactions": [], "err": {"Ok": null}},
+    ]))])
+    .await;
+    let sdk = JitoJsonRpcSDK::new(&server.url, None);
+
+    let outcomes = sdk
+        .get_bundle_outcomes(&ids(&["landed", "failed", "malformed", "unknown"]))
+        .await
+        .unwrap();
+
+    let ids: Vec<&str> = outcomes.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["landed", "failed", "malformed", "unknown"]);
+    assert!(matches!(&outcomes[0].1, Ok(Some(outcome)) if outcome.slot == Some(99)));
+    assert!(matches!(
+        &outcomes[1].1,
+        Err(JitoError::BundleFailed { .. })
+    ));
+    assert!(matches!(
+        &outcomes[2].1,
+        Err(JitoError::MalformedStatus { .. })
+    ));
+    assert!(matches!(&outcomes[3].1, Ok(None)));
+}
+
+#[tokio::test]
+async fn rejected_batch_is_retried_per_bundle() {
+    let invalid_id = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "error": {"code": -32602, "message": "invalid bundle id"},
+    })
+    .to_string();
+    let server = MockServer::start(vec![
+        Reply::json("200 OK", &invalid_id),
+        statuses(json!([landed("good")])),
+        Reply::json("200 OK", &invalid_id),
+    ])
+    .await;
+    let sdk = JitoJsonRpcSDK::new(&server.url, None);
+
+    let outcomes = sdk
+        .get_bundle_outcomes(&ids(&["good", "bad"]))
+        .await
+        .unwrap();
+
+    assert_eq!(server.requests().len(), 3);
+    assert_eq!(outcomes[0].0.as_str(), "good");
+    assert!(matches!(&outcomes[0].1, Ok(Some(_))));
+    assert_eq!(outcomes[1].0.as_str(), "bad");
+    assert!(matches!(
+        &outcomes[1].1,
+        Err(JitoError::RpcError { code: -32602, .. })
+    ));
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{MockServer, Reply};
+use jito_sdk_rust::{JitoError, JitoJsonRpcSDK};
+
+const OK: &str = r#"{"jsonrpc": "2.0", "id": 1, "result": []}"#;
+
+#[tokio::test]
+async fn slow_server_times_out_with_elapsed_and_limit() {
+    let limit = Duration::from_millis(200);
+    let server =
+        MockServer::start(vec![Reply::json("200 OK", OK).after(Duration::from_secs(2))]).await;
+    let sdk = JitoJsonRpcSDK::builder(&server.url)
+        .timeout(limit)
+        .build()
+        .unwrap();
+
+    match sdk.get_tip_accounts().await {
+        Err(JitoError::Timeout {
+            elapsed,
+            limit: reported,
+        }) => {
+            assert_eq!(reported, limit);
+            assert!(elapsed >= limit, "elapsed {:?} < limit", elapsed);
+            assert!(elapsed < Duration::from_secs(2), "elapsed {:?}", elapsed);
+        }
+        other => panic!("expected Timeout, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn method_timeout_applies_only_to_its_method() {
+    let server = MockServer::start(vec![
+        Reply::json("200 OK", OK).after(Duration::from_millis(400)),
+        Reply::json("200 OK", OK).after(Duration::from_millis(400)),
+    ])
+    .await;
+    let sdk = JitoJsonRpcSDK::builder(&server.url)
+        .method_timeout("getBundleStatuses", Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    assert!(sdk.get_tip_accounts().await.is_ok());
+    match sdk.get_bundle_statuses(vec!["abc".to_string()]).await {
+        Err(err) => assert!(
+            matches!(
+                err.downcast_ref::<JitoError>(),
+                Some(JitoError::Timeout { limit, .. }) if *limit == Duration::from_millis(100)
+            ),
+            "expected Timeout, got {:?}",
+            err
+        ),
+        Ok(value) => panic!("expected Timeout, got {}", value),
+    }
+}
@@ -0,0 +1,29 @@
+mod common;
+
+use common::{MockServer, Reply};
+use jito_sdk_rust::{JitoError, JitoJsonRpcSDK};
+
+const CORRUPT_TIP_ACCOUNTS: &str = r#"{"jsonrpc": "2.0", "id": 1, "result": ["not-a-pubkey"]}"#;
+
+#[tokio::test]
+async fn typed_tip_accounts_report_the_invalid_account() {
+    let server = MockServer::start(vec![Reply::json("200 OK", CORRUPT_TIP_ACCOUNTS)]).await;
+    let sdk = JitoJsonRpcSDK::new(&server.url, None);
+
+    match sdk.get_tip_accounts_typed().await {
+        Err(JitoError::InvalidTipAccount { value }) => assert_eq!(value, "\"not-a-pubkey\""),
+        other => panic!("expected InvalidTipAccount, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn random_tip_account_reports_the_invalid_account() {
+    let server = MockServer::start(vec![Reply::json("200 OK", CORRUPT_TIP_ACCOUNTS)]).await;
+    let sdk = JitoJsonRpcSDK::new(&server.url, None);
+
+    let err = sdk.get_random_tip_account().await.unwrap_err();
+    match err.downcast_ref::<JitoError>() {
+        Some(JitoError::InvalidTipAccount { value }) => assert_eq!(value, "\"not-a-pubkey\""),
+        other => panic!("expected InvalidTipAccount, got {:?}", other),
+    }
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use base64::{engine::general_purpose, Engine as _};
+use common::{MockServer, Reply};
+use jito_sdk_rust::{tip_ix, JitoError, JitoJsonRpcSDK, PreparedBundle, MAINNET_TIP_ACCOUNTS};
+use serde_json::json;
+use solana_hash::Hash;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+fn tip_transaction(lamports: u64) -> Vec<u8> {
+    let payer = Keypair::new();
+    let transaction = Transaction::new_signed_with_payer(
+        &[tip_ix(&payer.pubkey(), &MAINNET_TIP_ACCOUNTS[0], lamports)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        Hash::default(),
+    );
+    bincode::serialize(&transaction).unwrap()
+}
+
+fn capped_sdk(url: &str, cap: u64) -> JitoJsonRpcSDK {
+    JitoJsonRpcSDK::builder(url)
+        .max_tip_lamports(cap)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn encoded_bundle_over_the_cap_is_not_sent() {
+    let server = MockServer::start(vec![]).await;
+    let sdk = capped_sdk(&server.url, 1_000_000);
+    let params = json!([
+        general_purpose::STANDARD.encode(tip_transaction(400_000)),
+        general_purpose::STANDARD.encode(tip_transaction(400_000)),
+        general_purpose::STANDARD.encode(tip_transaction(400_000)),
+    ]);
+
+    match sdk.send_bundle_typed(Some(params), None).await {
+        Err(JitoError::TipTooHigh { requested, cap }) => {
+            assert_eq!((requested, cap), (1_200_000, 1_000_000));
+        }
+        other => panic!("expected TipTooHigh, got {:?}", other),
+    }
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn base58_params_are_decoded_for_the_cap() {
+    let server = MockServer::start(vec![]).await;
+    let sdk = capped_sdk(&server.url, 1_000_000);
+    let params = json!([
+        [bs58::encode(tip_transaction(5_000_000)).into_string()],
+        {"encoding": "base58"},
+    ]);
+
+    let err = sdk.send_bundle(Some(params), None).await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<JitoError>(),
+        Some(JitoError::TipTooHigh {
+            requested: 5_000_000,
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn encoded_bundle_within_the_cap_is_sent() {
+    let server = MockServer::start(vec![Reply::json(
+        "200 OK",
+        &json!({"jsonrpc": "2.0", "id": 1, "result": "bundle-1"}).to_string(),
+    )])
+    .await;
+    let sdk = capped_sdk(&server.url, 1_000_000);
+    let params = json!([general_purpose::STANDARD.encode(tip_transaction(10_000))]);
+
+    let bundle_id = sdk.send_bundle_typed(Some(params), None).await.unwrap();
+    assert_eq!(bundle_id.as_str(), "bundle-1");
+}
+
+#[tokio::test]
+async fn prepared_bundle_over_the_cap_is_not_sent() {
+    let server = MockServer::start(vec![]).await;
+    let sdk = capped_sdk(&server.url, 1_000_000);
+    let prepared = PreparedBundle::from_params(json!([
+        [general_purpose::STANDARD.encode(tip_transaction(2_000_000))],
+        {"encoding": "base64"},
+    ]))
+    .unwrap();
+
+    match sdk.send_bundle_prepared(&prepared).await {
+        Err(JitoError::TipTooHigh { requested, cap }) => {
+            assert_eq!((requested, cap), (2_000_000, 1_000_000));
+        }
+        other => panic!("expected TipTooHigh, got {:?}", other),
+    }
+    assert!(server.requests().is_empty());
+}
@@ -0,0 +1,22 @@
+use jito_sdk_rust::{JitoError, JitoJsonRpcSDK};
+
+#[tokio::test]
+async fn unresolvable_host_is_a_dns_resolution_error() {
+    let sdk = JitoJsonRpcSDK::new("http://block-engine.invalid/api/v1", None);
+
+    match sdk.get_tip_accounts().await {
+        Err(JitoError::DnsResolution { host, .. }) => assert_eq!(host, "block-engine.invalid"),
+        other => panic!("expected DnsResolution, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn multi_region_submit_without_regions_is_rejected() {
+    let sdk = JitoJsonRpcSDK::new("http://block-engine.invalid/api/v1", None);
+    let params = serde_json::json!(["AQ=="]);
+
+    match sdk.send_bundle_multi_region(Some(params), &[], None).await {
+        Err(JitoError::NoRegions) => {}
+        other => panic!("expected NoRegions, got {:?}", other),
+    }
+}